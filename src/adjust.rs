@@ -0,0 +1,133 @@
+use std::slice::Iter;
+
+use crate::color_conversions::{hsl2rgb, ColorNameStandard, MixSpace};
+use crate::{mix_with_alpha, parse_color_and_percent, ColorRepresentation};
+
+///One step of an adjust pipeline, named after the Sass color functions
+///amounts are in the same units as Sass: percent for saturation and lightness, degrees for
+///the hue and 0-1 for alpha
+#[derive(Clone)]
+pub enum Adjustment {
+    Lighten(f64),
+    Darken(f64),
+    Saturate(f64),
+    Desaturate(f64),
+    AdjustHue(f64),
+    Complement,
+    Grayscale,
+    Invert,
+    Alpha(f64),
+    Opacify(f64),
+    Transparentize(f64),
+    ///Mixes in percent of the color
+    Mix(ColorRepresentation, f64),
+}
+
+fn next_amount(args: &mut Iter<String>, name: &str) -> Result<f64, String> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("{} needs an amount", name))?;
+    value
+        .trim_end_matches("%")
+        .trim_end_matches("deg")
+        .parse()
+        .map_err(|_| format!("Invalid amount for {}: {}", name, value))
+}
+
+///Parses a list such as `lighten 10 saturate -5 rotate 30`
+pub fn parse(args: &[String], clr_std: &ColorNameStandard) -> Result<Vec<Adjustment>, String> {
+    let mut args = args.iter();
+    let mut adjustments = vec![];
+    while let Some(name) = args.next() {
+        let adjustment = match name.as_str() {
+            "lighten" => Adjustment::Lighten(next_amount(&mut args, name)?),
+            "darken" => Adjustment::Darken(next_amount(&mut args, name)?),
+            "saturate" => Adjustment::Saturate(next_amount(&mut args, name)?),
+            "desaturate" => Adjustment::Desaturate(next_amount(&mut args, name)?),
+            "adjust-hue" | "rotate" | "spin" => Adjustment::AdjustHue(next_amount(&mut args, name)?),
+            "complement" => Adjustment::Complement,
+            "grayscale" | "greyscale" => Adjustment::Grayscale,
+            "invert" => Adjustment::Invert,
+            "alpha" | "opacity" => Adjustment::Alpha(next_amount(&mut args, name)?),
+            "opacify" | "fade-in" => Adjustment::Opacify(next_amount(&mut args, name)?),
+            "transparentize" | "fade-out" => {
+                Adjustment::Transparentize(next_amount(&mut args, name)?)
+            }
+            "mix" => {
+                let with = args
+                    .next()
+                    .ok_or_else(|| "mix needs a color, eg: mix blue:30".to_owned())?;
+                let (clr_name, percent) = parse_color_and_percent(with)?;
+                Adjustment::Mix(
+                    ColorRepresentation::try_from_color(clr_name, clr_std)?,
+                    percent,
+                )
+            }
+            other => {
+                return Err(format!(
+                    "Unknown adjustment: {}, expected one of lighten, darken, saturate, desaturate, adjust-hue, complement, grayscale, invert, alpha, opacify, transparentize or mix",
+                    other
+                ))
+            }
+        };
+        adjustments.push(adjustment);
+    }
+    Ok(adjustments)
+}
+
+impl Adjustment {
+    pub fn apply(&self, clr: &ColorRepresentation) -> ColorRepresentation {
+        let (h, s, l) = clr.hsl();
+        //unlike modify_hsl this wraps the hue and doesn't ring the bell
+        let with_hsl = |h: f64, s: f64, l: f64| {
+            let (r, g, b) = hsl2rgb(
+                h.rem_euclid(360.0),
+                clamp!(0.0, s, 100.0),
+                clamp!(0.0, l, 100.0),
+            );
+            ColorRepresentation { r, g, b, a: clr.a }
+        };
+        let with_alpha = |a: f64| ColorRepresentation {
+            a: (clamp!(0.0, a, 1.0) * 255.0).round() as u8,
+            ..*clr
+        };
+        let alpha = clr.a as f64 / 255.0;
+        match self {
+            Self::Lighten(amount) => with_hsl(h, s, l + amount),
+            Self::Darken(amount) => with_hsl(h, s, l - amount),
+            Self::Saturate(amount) => with_hsl(h, s + amount, l),
+            Self::Desaturate(amount) => with_hsl(h, s - amount, l),
+            Self::AdjustHue(degrees) => with_hsl(h + degrees, s, l),
+            Self::Complement => with_hsl(h + 180.0, s, l),
+            Self::Grayscale => with_hsl(h, 0.0, l),
+            Self::Invert => ColorRepresentation {
+                r: 255.0 - clr.r,
+                g: 255.0 - clr.g,
+                b: 255.0 - clr.b,
+                a: clr.a,
+            },
+            Self::Alpha(a) => with_alpha(*a),
+            Self::Opacify(amount) => with_alpha(alpha + amount),
+            Self::Transparentize(amount) => with_alpha(alpha - amount),
+            Self::Mix(with, percent) => mix_with_alpha(clr, with, percent / 100.0, &MixSpace::RGB),
+        }
+    }
+
+    ///How the step is written on the command line, for the json output
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Lighten(amount) => format!("lighten {}", amount),
+            Self::Darken(amount) => format!("darken {}", amount),
+            Self::Saturate(amount) => format!("saturate {}", amount),
+            Self::Desaturate(amount) => format!("desaturate {}", amount),
+            Self::AdjustHue(degrees) => format!("adjust-hue {}", degrees),
+            Self::Complement => "complement".to_owned(),
+            Self::Grayscale => "grayscale".to_owned(),
+            Self::Invert => "invert".to_owned(),
+            Self::Alpha(a) => format!("alpha {}", a),
+            Self::Opacify(amount) => format!("opacify {}", amount),
+            Self::Transparentize(amount) => format!("transparentize {}", amount),
+            Self::Mix(with, percent) => format!("mix #{}:{}", with.tohex(with.a != 255), percent),
+        }
+    }
+}
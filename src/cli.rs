@@ -1,7 +1,20 @@
 use clap::ColorChoice;
 use clap::Parser;
 
+use crate::BlendMode;
 use crate::ColorNameStandard;
+use crate::ContrastAlgorithm;
+use crate::Deficiency;
+use crate::DeltaE;
+use crate::Harmony;
+use crate::HueInterpolation;
+use crate::InterpolationSpace;
+use crate::LightnessSpace;
+use crate::MixSpace;
+use crate::parse_severity;
+use crate::extract::ExtractMethod;
+use crate::random::{parse_hue_range, HueRange};
+use crate::ScaleKind;
 use crate::SelectionType;
 
 #[derive(Debug, PartialEq, Clone, clap::ValueEnum)]
@@ -24,7 +37,7 @@ pub struct Args {
         short,
         long,
         help = "Color naming standard",
-        long_help = "Color naming standard\nx11: Colors used in the X11 display server\nw3c: Colors standardized for the web\nThis is used to resolve conflicting names such as 'green'\nsee \x1b[34m\x1b]8;;https://en.wikipedia.org/wiki/X11_color_names#Clashes_between_web_and_X11_colors_in_the_CSS_color_scheme\x1b\\this wikipedia article\x1b]8;;\x07\x1b[0m for more information"
+        long_help = "Color naming standard\nx11: Colors used in the X11 display server\nw3c: Colors standardized for the web\nxterm: w3c with the xterm terminal colors\nmyterm: w3c with the colors of the current terminal\nA path to a file of color names (added to the w3c colors), each line being `R G B name` (like X11's rgb.txt) or `name = color`\nThis is used to resolve conflicting names such as 'green'\nsee \x1b[34m\x1b]8;;https://en.wikipedia.org/wiki/X11_color_names#Clashes_between_web_and_X11_colors_in_the_CSS_color_scheme\x1b\\this wikipedia article\x1b]8;;\x07\x1b[0m for more information"
     )]
     pub clr_standard: Option<ColorNameStandard>,
     #[arg(short = 'C', long, help = "Enables use of --bg-clr and --fg-clr")]
@@ -35,8 +48,20 @@ pub struct Args {
     pub fg_clr: Option<String>,
     #[arg(long = "cmp", help = "These colors will show up to compare against (seperate each color with a space)")]
     pub compare: Option<String>,
-    #[arg(short, long, help = "colors to mix with and display")]
+    #[arg(
+        long,
+        help = "The contrast algorithm shown next to the --cmp colors"
+    )]
+    pub contrast_algorithm: Option<ContrastAlgorithm>,
+    #[arg(
+        short,
+        long,
+        help = "colors to mix with and display (seperate each color with a space)",
+        long_help = "colors to mix with and display (seperate each color with a space)\neach color can be followed by :percent for how much of it to mix in, eg: red:20 blue"
+    )]
     pub mix_colors: Option<String>,
+    #[arg(long, help = "The space the --mix-colors are mixed in, by default it follows the input type")]
+    pub mix_space: Option<MixSpace>,
     #[arg(
         short,
         long,
@@ -58,6 +83,12 @@ pub struct Args {
         help = "Custom format for the CUSTOM format type"
     )]
     pub output_fmt: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Print machine readable json instead of text (for subcommands)"
+    )]
+    pub json: bool,
     #[command(subcommand)]
     pub action: Option<Actions>,
 }
@@ -72,14 +103,265 @@ pub enum Actions {
     #[command(about = "Inverts the given color")]
     Invert(InvertArgs),
     #[command(about = "Check contrast against other colors")]
-    Contrast(ContrastArgs)
+    Contrast(ContrastArgs),
+    #[command(about = "Simulate how colors look with a color vision deficiency")]
+    Simulate(SimulateArgs),
+    #[command(about = "Perceptual difference (delta E) between 2 colors")]
+    Diff(DiffArgs),
+    #[command(about = "Find the closest named colors")]
+    Name(NameArgs),
+    #[command(about = "Color harmonies made by rotating the hue")]
+    Harmony(HarmonyArgs),
+    #[command(about = "Generate a ramp of tints, shades or tones")]
+    Scale(ScaleArgs),
+    #[command(about = "Interpolate a gradient between colors")]
+    Gradient(GradientArgs),
+    #[command(about = "Blend a color on top of another")]
+    Blend(BlendArgs),
+    #[command(about = "Apply a chain of Sass style color functions")]
+    Adjust(AdjustArgs),
+    #[command(about = "Generate random colors")]
+    Random(RandomArgs),
+    #[command(about = "Extract the dominant colors of an image")]
+    Extract(ExtractArgs),
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct ExtractArgs {
+    #[arg(help = "A PNG, baseline JPEG or PPM/PGM image")]
+    pub image: std::path::PathBuf,
+    #[arg(short = 'n', long, default_value_t = 8, help = "How many colors to extract")]
+    pub count: usize,
+    #[arg(
+        short,
+        long,
+        default_value = "kmeans",
+        help = "How the colors are grouped, both work in OKLab",
+        long_help = "How the colors are grouped, both work in OKLab\nkmeans: finds the colors that best represent the image, the default\nmean-cut: like median cut, but splits the colors in two at the mean of their widest axis instead of the median, faster (median-cut also works)"
+    )]
+    pub method: ExtractMethod,
+    #[arg(short, long, help = "preview the colors in color squares")]
+    pub preview: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct RandomArgs {
+    #[arg(short = 'n', long, default_value_t = 1, help = "How many colors to generate")]
+    pub count: usize,
+    #[arg(long, help = "The same seed always gives the same colors, a random one is used if not given")]
+    pub seed: Option<u64>,
+    #[arg(
+        long,
+        value_parser = parse_hue_range,
+        help = "The OKLCH hue range in degrees, eg: 200-260, or 330-30 for the reds"
+    )]
+    pub hue: Option<HueRange>,
+    #[arg(long, help = "The minimum OKLCH lightness from 0-100")]
+    pub min_lightness: Option<f64>,
+    #[arg(long, help = "The maximum OKLCH lightness from 0-100")]
+    pub max_lightness: Option<f64>,
+    #[arg(long, help = "The minimum OKLCH chroma, from 0 to about 0.37")]
+    pub min_chroma: Option<f64>,
+    #[arg(long, help = "The maximum OKLCH chroma, from 0 to about 0.37")]
+    pub max_chroma: Option<f64>,
+    #[arg(long, help = "Only generate colors readable on this background")]
+    pub against: Option<String>,
+    #[arg(
+        long,
+        help = "The minimum contrast against --against, defaults to the algorithm's target (WCAG 4.5, APCA Lc 60)"
+    )]
+    pub min_contrast: Option<f64>,
+    #[arg(short, long, help = "The contrast algorithm for --min-contrast")]
+    pub algorithm: Option<ContrastAlgorithm>,
+    #[arg(
+        short,
+        long,
+        help = "Spread the hues out with the golden ratio, for categorical palettes where every color has to be distinct"
+    )]
+    pub golden: bool,
+    #[arg(short, long, help = "preview the colors in color squares")]
+    pub preview: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct AdjustArgs {
+    #[arg(short, long, help = "preview the color in a color square")]
+    pub preview: bool,
+    #[arg(help = "The color to adjust")]
+    pub color: String,
+    #[arg(
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        help = "The adjustments to apply in order, eg: lighten 10 saturate -5 rotate 30 alpha 0.8",
+        long_help = "The adjustments to apply in order, eg: lighten 10 saturate -5 rotate 30 alpha 0.8\nThese work like the Sass functions of the same name\nlighten, darken, saturate, desaturate <percent>: change the hsl lightness or saturation\nadjust-hue, rotate, spin <degrees>: rotate the hue\ncomplement, grayscale, invert: take no amount\nalpha, opacity <0-1>: set the alpha\nopacify, fade-in, transparentize, fade-out <0-1>: change the alpha\nmix <color:percent>: mix in a color, 50% by default"
+    )]
+    pub adjustments: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct BlendArgs {
+    #[arg(
+        help = "The color on top, composited with its alpha",
+        long_help = "The color on top, composited with its alpha\nthe alpha of rgba and hsla can be 0-255, 0-1 or a percentage, eg: rgba(255, 0, 0, 128), rgba(255, 0, 0, 0.5) or rgba(255, 0, 0, 50%)"
+    )]
+    pub top: String,
+    #[arg(help = "The color below")]
+    pub bottom: String,
+    #[arg(
+        short,
+        long,
+        default_value = "normal",
+        help = "The blend mode, as in css mix-blend-mode",
+        long_help = "The blend mode, as in css mix-blend-mode and the W3C compositing spec\nnormal just composites the top color over the bottom one, which flattens an rgba color over a background"
+    )]
+    pub mode: BlendMode,
+    #[arg(short, long, help = "preview the color in a color square")]
+    pub preview: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct GradientArgs {
+    #[arg(
+        required = true,
+        help = "The colors to go through, evenly spaced",
+        long_help = "The colors to go through, evenly spaced, at least 2 or a palette from the config"
+    )]
+    pub colors: Vec<String>,
+    #[arg(short, long, default_value_t = 7, help = "How many colors to output")]
+    pub steps: usize,
+    #[arg(
+        long,
+        default_value = "oklab",
+        help = "The space to interpolate in",
+        long_help = "The space to interpolate in, these follow the css color 4 interpolation rules\nsrgb: the gamma encoded channels, what most tools do\nlinear: linear light, physically accurate blending\noklab: perceptually even, the css default\noklch, hsl: polar spaces, the hue goes around the color wheel"
+    )]
+    pub space: InterpolationSpace,
+    #[arg(long, default_value = "shorter", help = "Which way around the hue wheel to go for oklch and hsl")]
+    pub hue: HueInterpolation,
+    #[arg(long, help = "Print a css linear-gradient() with the interpolated stops")]
+    pub css: bool,
+    #[arg(short, long, help = "preview the colors in color squares")]
+    pub preview: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct ScaleArgs {
+    #[arg(help = "The base color, the main color is used if not given")]
+    pub color: Option<String>,
+    #[arg(short, long, default_value_t = 11, help = "How many colors are in the scale")]
+    pub steps: usize,
+    #[arg(
+        short,
+        long,
+        default_value = "tonal",
+        help = "How the scale is made",
+        long_help = "How the scale is made\ntonal: light to dark in OKLCH with tailwind style 50-950 labels, the step closest in lightness to the color is the color itself\ntints, shades, tones: the color mixed towards white, black or gray in HSL, labeled by how far it's mixed in percent"
+    )]
+    pub kind: ScaleKind,
+    #[arg(long, help = "Print the scale as css custom properties")]
+    pub css: bool,
+    #[arg(long, default_value = "color", help = "The name used in the --css properties")]
+    pub name: String,
+    #[arg(short, long, help = "preview the colors in color squares")]
+    pub preview: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct HarmonyArgs {
+    #[arg(help = "The base color, the main color is used if not given")]
+    pub color: Option<String>,
+    #[arg(short, long, help = "The harmony to print, every harmony is printed if not given")]
+    pub kind: Option<Harmony>,
+    #[arg(short, long, help = "The space to rotate the hue in, defaults to hsl")]
+    pub space: Option<LightnessSpace>,
+    #[arg(short, long, help = "preview the colors in color squares")]
+    pub preview: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct NameArgs {
+    #[arg(help = "The color to name, the main color is used if not given")]
+    pub color: Option<String>,
+    #[arg(short, long, default_value_t = 1, help = "How many names to print")]
+    pub top: usize,
+    #[arg(short, long, help = "The delta E metric used to compare colors")]
+    pub method: Option<DeltaE>,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct DiffArgs {
+    pub a: String,
+    pub b: String,
+    #[arg(short, long, help = "Only print this delta E metric")]
+    pub method: Option<DeltaE>,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+pub struct SimulateArgs {
+    #[arg(short = 't', long = "type", help = "The color vision deficiency to simulate")]
+    pub kind: Deficiency,
+    #[arg(
+        short,
+        long,
+        default_value_t = 1.0,
+        value_parser = parse_severity,
+        help = "Severity from 0 to 1, below 1 simulates anomalous trichromacy"
+    )]
+    pub severity: f64,
+    #[arg(help = "The colors to simulate, the main color is used if none are given")]
+    pub colors: Vec<String>,
+    #[arg(short, long, help = "preview the original and simulated colors in color squares")]
+    pub preview: bool,
 }
 
 #[derive(Parser, Debug)]
 #[command()]
 pub struct ContrastArgs{
     #[arg(help = "The colors to contrast against")]
-    pub colors: Vec<String>
+    pub colors: Vec<String>,
+    #[arg(
+        long,
+        help = "Exit with a non-zero status if any ratio is below this",
+        long_help = "Exit with a non-zero status if any ratio is below this\nWCAG 2.x uses 4.5 for AA normal text, 7 for AAA normal text and 3 for large text and ui components\nWith APCA this is compared against the absolute Lc value, eg: 60 for content text"
+    )]
+    pub min: Option<f64>,
+    #[arg(short, long, help = "The contrast algorithm to use")]
+    pub algorithm: Option<ContrastAlgorithm>,
+    #[arg(
+        short,
+        long,
+        help = "Treat the given colors as text on top of the main color",
+        long_help = "Treat the given colors as text on top of the main color\nBy default the main color is the text and the given colors are the backgrounds, this matters for APCA which is polarity aware"
+    )]
+    pub reverse: bool,
+    #[arg(
+        long,
+        help = "Print the closest text color that reaches --min (or the algorithm's default target)"
+    )]
+    pub fix: bool,
+    #[arg(
+        long,
+        help = "The space to change lightness in for --fix, hue and saturation/chroma are kept"
+    )]
+    pub fix_space: Option<LightnessSpace>,
+    #[arg(
+        long,
+        help = "Contrast every given color against each other instead of the main color",
+        long_help = "Contrast every given color against each other instead of the main color\nEach row is the text color and each column is the background color"
+    )]
+    pub matrix: bool,
+    #[arg(long, help = "Print the --matrix as csv")]
+    pub csv: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -96,6 +378,22 @@ pub struct MixArgs {
     pub with: Vec<String>,
     #[arg(short, long, help = "preview the color in a color square")]
     pub preview: bool,
+    #[arg(
+        long,
+        default_value = "rgb",
+        help = "The space to mix in",
+        long_help = "The space to mix in, hsl and oklch take the shorter way around the hue wheel\nand transparent colors are mixed with premultiplied alpha"
+    )]
+    pub space: MixSpace,
+    #[arg(long, help = "Simulate a color vision deficiency on the mixed colors")]
+    pub simulate: Option<Deficiency>,
+    #[arg(
+        long,
+        default_value_t = 1.0,
+        value_parser = parse_severity,
+        help = "Severity from 0 to 1 for --simulate"
+    )]
+    pub severity: f64,
 }
 
 #[derive(Parser, Debug)]
@@ -0,0 +1,244 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use base64::engine::general_purpose;
+use base64::prelude::*;
+
+use crate::ConfigClipboard;
+use crate::{read_osc_response, send_osc};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Selection {
+    Clipboard,
+    ///The X11/wayland primary selection, what's highlighted with the mouse
+    Primary,
+}
+
+pub trait Clipboard {
+    fn copy(&self, data: &str, selection: Selection) -> Result<(), String>;
+    fn paste(&self, selection: Selection) -> Result<String, String>;
+}
+
+///Uses the terminal's clipboard, works over ssh but most terminals don't allow reading it
+pub struct Osc52;
+
+impl Osc52 {
+    fn target(selection: Selection) -> char {
+        match selection {
+            Selection::Clipboard => 'c',
+            Selection::Primary => 'p',
+        }
+    }
+}
+
+impl Clipboard for Osc52 {
+    fn copy(&self, data: &str, selection: Selection) -> Result<(), String> {
+        let b64 = general_purpose::STANDARD.encode(data);
+        send_osc(&format!("52;{};{}", Osc52::target(selection), b64));
+        Ok(())
+    }
+
+    fn paste(&self, selection: Selection) -> Result<String, String> {
+        let unsupported = || "This terminal does not support reading the clipboard".to_owned();
+        send_osc(&format!("52;{};?", Osc52::target(selection)));
+        let clip_buf = read_osc_response(7).ok_or_else(unsupported)?;
+        let clip_data = clip_buf.split(";").nth(2).ok_or_else(unsupported)?;
+        let bytes = general_purpose::STANDARD
+            .decode(clip_data)
+            .map_err(|_| unsupported())?;
+        String::from_utf8(bytes).map_err(|_| "The clipboard does not contain text".to_owned())
+    }
+}
+
+///Pipes the data into a command to copy, and reads the output of a command to paste
+///eg: wl-copy and wl-paste
+pub struct CommandClipboard {
+    copy: Option<Vec<String>>,
+    paste: Option<Vec<String>>,
+    copy_primary: Option<Vec<String>>,
+    paste_primary: Option<Vec<String>>,
+}
+
+///The command as it should appear in error messages
+fn command_name(args: &[String]) -> &str {
+    match args {
+        [sh, flag, cmd] if sh == "sh" && flag == "-c" => cmd,
+        _ => &args[0],
+    }
+}
+
+impl CommandClipboard {
+    ///Takes the copy and paste commands for the clipboard and then the primary selection
+    fn new(commands: [&[&str]; 4]) -> CommandClipboard {
+        let [copy, paste, copy_primary, paste_primary] =
+            commands.map(|args| Some(args.iter().map(|arg| arg.to_string()).collect()));
+        CommandClipboard {
+            copy,
+            paste,
+            copy_primary,
+            paste_primary,
+        }
+    }
+
+    ///Commands from the config are run with `sh -c`
+    fn from_shell(cfg: &ConfigClipboard) -> CommandClipboard {
+        let sh = |cmd: &String| vec!["sh".to_owned(), "-c".to_owned(), cmd.to_owned()];
+        CommandClipboard {
+            copy: cfg.copy.as_ref().map(sh),
+            paste: cfg.paste.as_ref().map(sh),
+            copy_primary: cfg.copy_primary.as_ref().map(sh),
+            paste_primary: cfg.paste_primary.as_ref().map(sh),
+        }
+    }
+}
+
+impl Clipboard for CommandClipboard {
+    fn copy(&self, data: &str, selection: Selection) -> Result<(), String> {
+        let args = match selection {
+            Selection::Clipboard => &self.copy,
+            Selection::Primary => &self.copy_primary,
+        };
+        let Some(args) = args else {
+            return Err(format!("No copy command is configured for the {:?} selection", selection));
+        };
+        //the output goes nowhere, tools such as xclip keep running in the background
+        let mut child = Command::new(&args[0])
+            .args(&args[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| format!("Could not run {}: {}", command_name(args), err))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(data.as_bytes());
+        }
+        match child.wait() {
+            Ok(status) if status.success() => Ok(()),
+            _ => Err(format!("{} failed to copy", command_name(args))),
+        }
+    }
+
+    fn paste(&self, selection: Selection) -> Result<String, String> {
+        let args = match selection {
+            Selection::Clipboard => &self.paste,
+            Selection::Primary => &self.paste_primary,
+        };
+        let Some(args) = args else {
+            return Err(format!("No paste command is configured for the {:?} selection", selection));
+        };
+        let output = Command::new(&args[0])
+            .args(&args[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|err| format!("Could not run {}: {}", command_name(args), err))?;
+        if !output.status.success() {
+            return Err(format!("{} failed to paste", command_name(args)));
+        }
+        String::from_utf8(output.stdout).map_err(|_| "The clipboard does not contain text".to_owned())
+    }
+}
+
+///Copies to and pastes from a plain file
+pub struct FileClipboard(PathBuf);
+
+impl Clipboard for FileClipboard {
+    fn copy(&self, data: &str, selection: Selection) -> Result<(), String> {
+        if selection == Selection::Primary {
+            return Err("The file clipboard has no primary selection".to_owned());
+        }
+        std::fs::write(&self.0, data)
+            .map_err(|err| format!("Could not write {}: {}", self.0.display(), err))
+    }
+
+    fn paste(&self, selection: Selection) -> Result<String, String> {
+        if selection == Selection::Primary {
+            return Err("The file clipboard has no primary selection".to_owned());
+        }
+        std::fs::read_to_string(&self.0)
+            .map_err(|err| format!("Could not read {}: {}", self.0.display(), err))
+    }
+}
+
+fn in_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+fn wl_clipboard() -> CommandClipboard {
+    CommandClipboard::new([
+        &["wl-copy"],
+        &["wl-paste", "--no-newline"],
+        &["wl-copy", "--primary"],
+        &["wl-paste", "--primary", "--no-newline"],
+    ])
+}
+
+fn xclip() -> CommandClipboard {
+    CommandClipboard::new([
+        &["xclip", "-selection", "clipboard"],
+        &["xclip", "-selection", "clipboard", "-o"],
+        &["xclip", "-selection", "primary"],
+        &["xclip", "-selection", "primary", "-o"],
+    ])
+}
+
+fn xsel() -> CommandClipboard {
+    CommandClipboard::new([
+        &["xsel", "--clipboard", "--input"],
+        &["xsel", "--clipboard", "--output"],
+        &["xsel", "--primary", "--input"],
+        &["xsel", "--primary", "--output"],
+    ])
+}
+
+///Picks the first backend that is usable in this session
+///A configured command or file always wins, then wl-clipboard on wayland, then xclip/xsel on X11
+fn auto(cfg: Option<&ConfigClipboard>) -> Box<dyn Clipboard> {
+    if let Some(cfg) = cfg {
+        if cfg.copy.is_some() || cfg.paste.is_some() {
+            return Box::new(CommandClipboard::from_shell(cfg));
+        }
+        if let Some(file) = &cfg.file {
+            return Box::new(FileClipboard(file.clone()));
+        }
+    }
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() && in_path("wl-copy") {
+        return Box::new(wl_clipboard());
+    }
+    if std::env::var_os("DISPLAY").is_some() {
+        if in_path("xclip") {
+            return Box::new(xclip());
+        }
+        if in_path("xsel") {
+            return Box::new(xsel());
+        }
+    }
+    Box::new(Osc52)
+}
+
+pub fn from_config(cfg: Option<&ConfigClipboard>) -> Result<Box<dyn Clipboard>, String> {
+    let backend = cfg
+        .and_then(|cfg| cfg.backend.as_deref())
+        .unwrap_or("auto");
+    Ok(match backend {
+        "auto" => auto(cfg),
+        "osc52" => Box::new(Osc52),
+        "wl-clipboard" => Box::new(wl_clipboard()),
+        "xclip" => Box::new(xclip()),
+        "xsel" => Box::new(xsel()),
+        "command" => Box::new(CommandClipboard::from_shell(cfg.unwrap())),
+        "file" => match cfg.and_then(|cfg| cfg.file.clone()) {
+            Some(file) => Box::new(FileClipboard(file)),
+            None => return Err("The file clipboard backend needs clipboard.file to be set".to_owned()),
+        },
+        other => {
+            return Err(format!(
+                "Unknown clipboard backend: {}, expected one of auto, osc52, wl-clipboard, xclip, xsel, command or file",
+                other
+            ))
+        }
+    })
+}
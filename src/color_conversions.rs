@@ -1,6 +1,9 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
-use crate::{hashmap, read_ansi_color};
+use crate::color_names::{CLASH_ALIASES, CSS_COLORS, LEGACY_NAMES, X11_COLORS};
+use crate::{exit_with_error, hashmap, terminal_palette, ColorRepresentation};
 
 pub type ColorInt = u64;
 ///Number from 0-1
@@ -33,6 +36,332 @@ pub fn contrast(col1: [f64; 3], col2: [f64; 3]) -> f64 {
     return (brightest + 0.05) / (darkest + 0.05);
 }
 
+///WCAG 2.x success criteria and the minimum contrast ratio each one needs
+pub const WCAG_THRESHOLDS: [(&str, f64); 5] = [
+    ("AA normal", 4.5),
+    ("AAA normal", 7.0),
+    ("AA large", 3.0),
+    ("AAA large", 4.5),
+    ("AA ui", 3.0),
+];
+
+///Checks a contrast ratio against every criteria in WCAG_THRESHOLDS
+pub fn wcag_report(ratio: f64) -> Vec<(&'static str, bool)> {
+    WCAG_THRESHOLDS
+        .iter()
+        .map(|(name, min)| (*name, ratio >= *min))
+        .collect()
+}
+
+///APCA (0.0.98G-4g) screen luminance, including the soft clamp for near black colors
+fn apca_luminance(color: [f64; 3]) -> f64 {
+    const BLACK_THRESHOLD: f64 = 0.022;
+    const BLACK_CLAMP: f64 = 1.414;
+    let y = (color[0] / 255.0).powf(2.4) * 0.2126729
+        + (color[1] / 255.0).powf(2.4) * 0.7151522
+        + (color[2] / 255.0).powf(2.4) * 0.0721750;
+    if y < BLACK_THRESHOLD {
+        y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+    } else {
+        y
+    }
+}
+
+///APCA lightness contrast (Lc) of text drawn on bg
+///positive values are dark text on a light background, negative values are light text on a dark
+///background
+pub fn apca_contrast(text: [f64; 3], bg: [f64; 3]) -> f64 {
+    const SCALE: f64 = 1.14;
+    const LOW_OFFSET: f64 = 0.027;
+    const LOW_CLIP: f64 = 0.1;
+    let text_y = apca_luminance(text);
+    let bg_y = apca_luminance(bg);
+    if (bg_y - text_y).abs() < 0.0005 {
+        return 0.0;
+    }
+    let lc = if bg_y > text_y {
+        let sapc = (bg_y.powf(0.56) - text_y.powf(0.57)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        let sapc = (bg_y.powf(0.65) - text_y.powf(0.62)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+    return lc * 100.0;
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum ContrastAlgorithm {
+    WCAG,
+    APCA,
+}
+
+impl ContrastAlgorithm {
+    pub fn contrast(&self, text: [f64; 3], bg: [f64; 3]) -> f64 {
+        match self {
+            Self::WCAG => contrast(text, bg),
+            Self::APCA => apca_contrast(text, bg),
+        }
+    }
+
+    pub fn label(&self, value: f64) -> String {
+        match self {
+            Self::WCAG => format!("{:.2}:1", value),
+            Self::APCA => format!("Lc {:.1}", value),
+        }
+    }
+
+    ///APCA values are signed by polarity, so only the magnitude is checked
+    pub fn passes(&self, value: f64, min: f64) -> bool {
+        match self {
+            Self::WCAG => value >= min,
+            Self::APCA => value.abs() >= min,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::WCAG => Self::APCA,
+            Self::APCA => Self::WCAG,
+        }
+    }
+
+    ///The target used when none is given, WCAG AA for normal text and APCA Lc 60 for content text
+    pub fn default_target(&self) -> f64 {
+        match self {
+            Self::WCAG => 4.5,
+            Self::APCA => 60.0,
+        }
+    }
+}
+
+///A cylindrical space to change one channel of a color in while the others are kept
+///eg: the lightness when fixing contrast or the hue for harmonies
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum LightnessSpace {
+    HSL,
+    OKLCH,
+}
+
+impl LightnessSpace {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::HSL => Self::OKLCH,
+            Self::OKLCH => Self::HSL,
+        }
+    }
+}
+
+///Rotates the hue of an rgb color by degrees
+pub fn rotate_hue(clr: [f64; 3], degrees: f64, space: &LightnessSpace) -> [f64; 3] {
+    let (r, g, b) = match space {
+        LightnessSpace::HSL => {
+            let (h, s, l) = rgb2hsl(clr[0], clr[1], clr[2]);
+            hsl2rgb((h + degrees).rem_euclid(360.0), s, l)
+        }
+        LightnessSpace::OKLCH => {
+            let (l, c, h) = rgb2oklch(clr[0], clr[1], clr[2]);
+            oklch2rgb_in_gamut(l, c, (h + degrees).rem_euclid(360.0))
+        }
+    };
+    //whole rgb values, the same as the colors get printed and compared as
+    [r, g, b].map(f64::round)
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum Harmony {
+    Complementary,
+    Triadic,
+    Tetradic,
+    Analogous,
+    SplitComplementary,
+    Square,
+}
+
+impl Harmony {
+    pub const ALL: [Harmony; 6] = [
+        Self::Complementary,
+        Self::Triadic,
+        Self::Tetradic,
+        Self::Analogous,
+        Self::SplitComplementary,
+        Self::Square,
+    ];
+
+    ///Hue rotations from the base color, the base color (0) is first
+    pub fn offsets(&self) -> &'static [f64] {
+        match self {
+            Self::Complementary => &[0.0, 180.0],
+            Self::Triadic => &[0.0, 120.0, 240.0],
+            //the rectangle variant, 2 complementary pairs 60 degrees apart
+            Self::Tetradic => &[0.0, 60.0, 180.0, 240.0],
+            Self::Analogous => &[0.0, -30.0, 30.0],
+            Self::SplitComplementary => &[0.0, 150.0, 210.0],
+            Self::Square => &[0.0, 90.0, 180.0, 270.0],
+        }
+    }
+
+    ///cycles through every harmony, None being no harmony
+    pub fn next(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::ALL[0]),
+            Some(harmony) => {
+                let idx = Self::ALL.iter().position(|h| *h == harmony).unwrap();
+                Self::ALL.get(idx + 1).copied()
+            }
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum ScaleKind {
+    ///Light to dark in OKLCH, like tailwind's 50-950 palettes
+    Tonal,
+    ///Towards white in HSL
+    Tints,
+    ///Towards black in HSL
+    Shades,
+    ///Towards gray in HSL
+    Tones,
+}
+
+///OKLCH lightness of the 50-950 steps of tailwind's blue, tonal scales follow this curve
+const TONAL_LIGHTNESS: [f64; 11] = [
+    0.97, 0.932, 0.882, 0.809, 0.707, 0.623, 0.546, 0.488, 0.424, 0.379, 0.282,
+];
+
+///The lightness at t (0 to 1) along TONAL_LIGHTNESS
+fn tonal_lightness(t: f64) -> f64 {
+    let x = t * (TONAL_LIGHTNESS.len() - 1) as f64;
+    let i = (x.floor() as usize).min(TONAL_LIGHTNESS.len() - 2);
+    TONAL_LIGHTNESS[i] + (TONAL_LIGHTNESS[i + 1] - TONAL_LIGHTNESS[i]) * (x - i as f64)
+}
+
+///A ramp of steps colors based on clr
+///tonal scales go from light to dark and contain clr at the step closest to its lightness,
+///the others start with clr and go towards white, black or gray without reaching it
+pub fn scale(clr: [f64; 3], steps: usize, kind: &ScaleKind) -> Vec<[f64; 3]> {
+    if let ScaleKind::Tonal = kind {
+        let (l, c, h) = rgb2oklch(clr[0], clr[1], clr[2]);
+        let position = |i: usize| match steps {
+            1 => 0.5,
+            _ => i as f64 / (steps - 1) as f64,
+        };
+        let Some(base) = (0..steps).min_by(|a, b| {
+            let distance = |i: usize| (tonal_lightness(position(i)) - l).abs();
+            distance(*a).total_cmp(&distance(*b))
+        }) else {
+            return vec![];
+        };
+        let base_t = position(base);
+        return (0..steps)
+            .map(|i| {
+                if i == base {
+                    return clr;
+                }
+                let t = position(i);
+                //chroma eases off away from clr, quickly towards the light end and slowly towards
+                //the dark end, fitted to tailwind's palettes
+                let chroma = if t < base_t {
+                    let distance = (base_t - t) / base_t;
+                    c * (0.07 + 0.93 * (1.0 - distance).powf(1.2))
+                } else {
+                    let distance = (t - base_t) / (1.0 - base_t);
+                    c * (1.0 - 0.57 * distance.powi(2))
+                };
+                let (r, g, b) = oklch2rgb_in_gamut(tonal_lightness(t), chroma, h);
+                [r, g, b]
+            })
+            .collect();
+    }
+    let (h, s, l) = rgb2hsl(clr[0], clr[1], clr[2]);
+    (0..steps)
+        .map(|i| {
+            let amount = i as f64 / steps as f64;
+            let (r, g, b) = match kind {
+                ScaleKind::Tints => hsl2rgb(h, s, l + (100.0 - l) * amount),
+                ScaleKind::Shades => hsl2rgb(h, s, l * (1.0 - amount)),
+                _ => hsl2rgb(h, s * (1.0 - amount), l),
+            };
+            [r, g, b]
+        })
+        .collect()
+}
+
+///The colors of a harmony, starting with clr itself
+pub fn harmony(clr: [f64; 3], kind: &Harmony, space: &LightnessSpace) -> Vec<[f64; 3]> {
+    kind.offsets()
+        .iter()
+        .map(|degrees| match degrees {
+            //avoid rounding errors on the base color
+            0.0 => clr,
+            _ => rotate_hue(clr, *degrees, space),
+        })
+        .collect()
+}
+
+///Finds the color with the closest lightness to text that reaches target against bg
+///returns None if no lightness passes
+pub fn fix_contrast(
+    text: [f64; 3],
+    bg: [f64; 3],
+    target: f64,
+    algorithm: &ContrastAlgorithm,
+    space: &LightnessSpace,
+) -> Option<[f64; 3]> {
+    let passes = |clr: [f64; 3]| algorithm.passes(algorithm.contrast(clr, bg), target);
+    if passes(text) {
+        return Some(text);
+    }
+    //(current lightness, max lightness, function to get the rgb of a lightness)
+    let (l, max_l, with_lightness): (f64, f64, Box<dyn Fn(f64) -> [f64; 3]>) = match space {
+        LightnessSpace::HSL => {
+            let (h, s, l) = rgb2hsl(text[0], text[1], text[2]);
+            (
+                l,
+                100.0,
+                Box::new(move |l| {
+                    let (r, g, b) = hsl2rgb(h, s, l);
+                    [r, g, b]
+                }),
+            )
+        }
+        LightnessSpace::OKLCH => {
+            let (l, c, h) = rgb2oklch(text[0], text[1], text[2]);
+            (
+                l,
+                1.0,
+                Box::new(move |l| {
+                    let (r, g, b) = oklch2rgb(l, c, h);
+                    [r, g, b]
+                }),
+            )
+        }
+    };
+    let step = max_l / 1000.0;
+    for i in 1..=1000 {
+        for direction in [-1.0, 1.0] {
+            let new_l = l + step * i as f64 * direction;
+            if new_l < 0.0 || new_l > max_l {
+                continue;
+            }
+            //check the color that gets printed, not one between two rgb values
+            let clr = with_lightness(new_l).map(f64::round);
+            if passes(clr) {
+                return Some(clr);
+            }
+        }
+    }
+    None
+}
+
 pub fn hsl2rgb(mut h: f64, mut s: f64, mut l: f64) -> (f64, f64, f64) {
     s /= 100.0;
     l /= 100.0;
@@ -113,6 +442,157 @@ pub fn rgb2hsl(mut r: f64, mut g: f64, mut b: f64) -> (f64, f64, f64) {
     return (h, s * 100.0, l * 100.0);
 }
 
+///sRGB channel from 0-1 to linear light from 0-1
+pub fn srgb2linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+///linear light from 0-1 to an sRGB channel from 0-1
+pub fn linear2srgb(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+///rgb from 0-255 to OKLab with L from 0-1
+pub fn rgb2oklab(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let (r, g, b) = (
+        srgb2linear(r / 255.0),
+        srgb2linear(g / 255.0),
+        srgb2linear(b / 255.0),
+    );
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    return (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    );
+}
+
+///OKLab to linear rgb from 0-1, the result may be out of gamut
+pub fn oklab2linear(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    return (
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    );
+}
+
+///OKLab to rgb from 0-255, out of gamut channels are clamped
+pub fn oklab2rgb(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let (r, g, b) = oklab2linear(l, a, b);
+    let to_srgb = |c: f64| (clamp!(0.0, linear2srgb(c), 1.0) * 255.0).round();
+    return (to_srgb(r), to_srgb(g), to_srgb(b));
+}
+
+pub fn oklab2oklch(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let mut h = b.atan2(a).to_degrees();
+    if h < 0.0 {
+        h += 360.0;
+    }
+    return (l, (a * a + b * b).sqrt(), h);
+}
+
+pub fn oklch2oklab(l: f64, c: f64, h: f64) -> (f64, f64, f64) {
+    let h = h.to_radians();
+    return (l, c * h.cos(), c * h.sin());
+}
+
+pub fn rgb2oklch(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let (l, a, b) = rgb2oklab(r, g, b);
+    return oklab2oklch(l, a, b);
+}
+
+pub fn oklch2rgb(l: f64, c: f64, h: f64) -> (f64, f64, f64) {
+    let (l, a, b) = oklch2oklab(l, c, h);
+    return oklab2rgb(l, a, b);
+}
+
+///Whether an OKLCH color fits in sRGB, with a little room for rounding errors
+pub fn oklch_in_gamut(l: f64, c: f64, h: f64) -> bool {
+    let (l, a, b) = oklch2oklab(l, c, h);
+    let (r, g, b) = oklab2linear(l, a, b);
+    [r, g, b].iter().all(|channel| (-0.0001..=1.0001).contains(channel))
+}
+
+///Like oklch2rgb but out of gamut colors lose chroma instead of being clamped, which keeps the
+///lightness and hue
+pub fn oklch2rgb_in_gamut(l: f64, c: f64, h: f64) -> (f64, f64, f64) {
+    let in_gamut = |c: f64| oklch_in_gamut(l, c, h);
+    if in_gamut(c) {
+        return oklch2rgb(l, c, h);
+    }
+    let (mut low, mut high) = (0.0, c);
+    for _ in 0..20 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    return oklch2rgb(l, low, h);
+}
+
+const D65_WHITE: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+///rgb from 0-255 to CIELAB (D65)
+pub fn rgb2lab(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let (r, g, b) = (
+        srgb2linear(r / 255.0),
+        srgb2linear(g / 255.0),
+        srgb2linear(b / 255.0),
+    );
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / D65_WHITE[0];
+    let y = (0.2126729 * r + 0.7151522 * g + 0.0721750 * b) / D65_WHITE[1];
+    let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / D65_WHITE[2];
+    let f = |t: f64| {
+        const DELTA: f64 = 6.0 / 29.0;
+        if t > DELTA.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    return (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz));
+}
+
+///CIELAB (D65) to rgb from 0-255, out of gamut channels are clamped
+pub fn lab2rgb(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let fy = (l + 16.0) / 116.0;
+    let (fx, fz) = (fy + a / 500.0, fy - b / 200.0);
+    let f_inv = |t: f64| {
+        const DELTA: f64 = 6.0 / 29.0;
+        if t > DELTA {
+            t.powi(3)
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+    let (x, y, z) = (
+        f_inv(fx) * D65_WHITE[0],
+        f_inv(fy) * D65_WHITE[1],
+        f_inv(fz) * D65_WHITE[2],
+    );
+    let r = 3.2404542 * x - 1.5371385 * y - 0.4985314 * z;
+    let g = -0.9692660 * x + 1.8760108 * y + 0.0415560 * z;
+    let b = 0.0556434 * x - 0.2040259 * y + 1.0572252 * z;
+    let to_srgb = |c: f64| (clamp!(0.0, linear2srgb(c), 1.0) * 255.0).round();
+    return (to_srgb(r), to_srgb(g), to_srgb(b));
+}
+
 //REMOVE the # before giving to this function
 pub fn hex62rgb(hex: &str) -> (u8, u8, u8) {
     let r = i64::from_str_radix(&hex[0..2], 16).unwrap();
@@ -186,16 +666,103 @@ pub fn rgb2ansi256(r: u8, g: u8, b: u8) -> u8 {
         .unwrap();
 }
 
-#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ColorNameStandard {
     X11,
     W3C,
     XTerm,
     MyTerm,
+    ///A file of color names on top of the w3c colors, see read_color_name_file
+    Custom(PathBuf),
+}
+
+impl std::str::FromStr for ColorNameStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "x11" => Ok(Self::X11),
+            "w3c" => Ok(Self::W3C),
+            "xterm" => Ok(Self::XTerm),
+            "myterm" => Ok(Self::MyTerm),
+            _ if Path::new(s).is_file() => Ok(Self::Custom(PathBuf::from(s))),
+            _ => Err(format!(
+                "'{}' is not x11, w3c, xterm, myterm or a color name file",
+                s
+            )),
+        }
+    }
+}
+
+///Reads a list of color names, each line is either in the X11 rgb.txt format (`R G B name`)
+///or `name = color` where color is any supported color format
+///lines starting with ! are comments
+fn read_color_name_file(path: &Path) -> HashMap<String, [u8; 3]> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) => exit_with_error(&format!("Could not read {}: {}", path.display(), err)),
+    };
+    let mut colors = HashMap::new();
+    for line in data.lines().map(|line| line.trim()) {
+        if line.is_empty() || line.starts_with("!") {
+            continue;
+        }
+        if let Some((name, clr)) = line.split_once("=") {
+            match ColorRepresentation::try_from_color(clr.trim(), &ColorNameStandard::W3C) {
+                Ok(clr) => {
+                    colors.insert(name.trim().to_string(), [clr.r as u8, clr.g as u8, clr.b as u8]);
+                }
+                Err(err) => exit_with_error(&format!("{}: {}", path.display(), err)),
+            }
+            continue;
+        }
+        let mut items = line.split_whitespace();
+        let rgb: Vec<u8> = items.by_ref().take(3).filter_map(|n| n.parse().ok()).collect();
+        let name = items.collect::<Vec<&str>>().join(" ");
+        if rgb.len() != 3 || name.is_empty() {
+            exit_with_error(&format!("{}: Invalid line: {}", path.display(), line));
+        }
+        colors.insert(name, [rgb[0], rgb[1], rgb[2]]);
+    }
+    colors
 }
 
+///Color names are matched ignoring case, spaces, dashes and underscores
+///so "alice blue", "AliceBlue" and "alice-blue" are the same color
+pub fn normalize_color_name(name: &str) -> String {
+    name.to_lowercase()
+        .replace(|ch| ch == ' ' || ch == '-' || ch == '_', "")
+}
+
+static USER_COLORS: OnceLock<HashMap<String, [u8; 3]>> = OnceLock::new();
+
+///Sets the colors defined in the config, these are checked before any ColorNameStandard
+pub fn set_user_colors(colors: HashMap<String, [u8; 3]>) {
+    let _ = USER_COLORS.set(colors);
+}
+
+pub fn user_colors() -> Option<&'static HashMap<String, [u8; 3]>> {
+    USER_COLORS.get()
+}
+
+static NAME_TABLES: OnceLock<Mutex<HashMap<ColorNameStandard, &'static HashMap<String, [u8; 3]>>>> =
+    OnceLock::new();
+
 impl ColorNameStandard {
-    pub fn list_colors(&self) -> HashMap<&str, [u8; 3]> {
+    ///Every color name in this standard, the table is only built once per standard
+    pub fn list_colors(&self) -> &'static HashMap<String, [u8; 3]> {
+        let tables = NAME_TABLES.get_or_init(|| Mutex::new(HashMap::new()));
+        if let Some(table) = tables.lock().unwrap().get(self) {
+            return table;
+        }
+        //the lock isn't held while building, the colors of a name file can be w3c names which
+        //need the w3c table
+        //only a handful of standards can exist per run, so leaking them is fine
+        let table: &'static HashMap<String, [u8; 3]> = Box::leak(Box::new(self.build_colors()));
+        tables.lock().unwrap().entry(self.clone()).or_insert(table)
+    }
+
+    fn build_colors(&self) -> HashMap<String, [u8; 3]> {
         type TermClrConvertFn = fn(&ColorNameStandard) -> [u8; 3];
         let low_color_map: HashMap<[&str; 3], Box<fn(&ColorNameStandard) -> [u8; 3]>> = hashmap! {
             ["black", "0", "30"] => Box::new(ColorNameStandard::black as TermClrConvertFn),
@@ -217,202 +784,121 @@ impl ColorNameStandard {
             ["bright white", "97"] => Box::new(ColorNameStandard::bright_white as TermClrConvertFn),
             ["bright yellow", "93"] => Box::new(ColorNameStandard::bright_yellow as TermClrConvertFn),
         };
-        let mut data: HashMap<&str, [u8; 3]> = hashmap! {
-            "alice blue" => [0xf0, 0xf8, 0xff],
-            "antique white" => [0xfa, 0xeb, 0xd7],
-            "aqua" => [0x00, 0xff, 0xff],
-            "aquamarine" => [0x7f, 0xff, 0xd4],
-            "azure" => [0xf0, 0xff, 0xff],
-            "beige" => [0xf5, 0xf5, 0xdc],
-            "bisque" => [0xff, 0xe4, 0xc4],
-            "blanched almond" => [0xff, 0xeb, 0xcd],
-            "blue violet" => [0x8a, 0x2b, 0xe2],
-            "brown" => [0xa5, 0x2a, 0x2a],
-            "burlywood" => [0xde, 0xb8, 0x87],
-            "cadet blue" => [0x5f, 0x9e, 0xa0],
-            "chartreuse" => [0x7f, 0xff, 0x00],
-            "chocolate" => [0xd2, 0x69, 0x1e],
-            "coral" => [0xff, 0x7f, 0x50],
-            "cornflower blue" => [0x64, 0x95, 0xed],
-            "cornsilk" => [0xff, 0xf8, 0xdc],
-            "crimson" => [0xdc, 0x14, 0x3c],
-            "dark blue" => [0x00, 0x00, 0x8b],
-            "dark cyan" => [0x00, 0x8b, 0x8b],
-            "dark goldenrod" => [0xb8, 0x86, 0x0b],
-            "dark gray" => [0xa9, 0xa9, 0xa9],
-            "dark green" => [0x00, 0x64, 0x00],
-            "dark khaki" => [0xbd, 0xb7, 0x6b],
-            "dark magenta" => [0x8b, 0x00, 0x8b],
-            "dark olive green" => [0x55, 0x6b, 0x2f],
-            "dark orange" => [0xff, 0x8c, 0x00],
-            "dark orchid" => [0x99, 0x32, 0xcc],
-            "dark red" => [0x8b, 0x00, 0x00],
-            "dark salmon" => [0xe9, 0x96, 0x7a],
-            "dark sea green" => [0x8f, 0xbc, 0x8f],
-            "dark slate blue" => [0x48, 0x3d, 0x8b],
-            "dark slate gray" => [0x2f, 0x4f, 0x4f],
-            "dark turquoise" => [0x00, 0xce, 0xd1],
-            "dark violet" => [0x94, 0x00, 0xd3],
-            "deep pink" => [0xff, 0x14, 0x93],
-            "deep sky blue" => [0x00, 0xbf, 0xff],
-            "dim gray" => [0x69, 0x69, 0x69],
-            "dodger blue" => [0x1e, 0x90, 0xff],
-            "firebrick" => [0xb2, 0x22, 0x22],
-            "floral white" => [0xff, 0xfa, 0xf0],
-            "forest green" => [0x22, 0x8b, 0x22],
-            "fuchsia" => [0xff, 0x00, 0xff],
-            "gainsboro*" => [0xdc, 0xdc, 0xdc],
-            "ghost white" => [0xf8, 0xf8, 0xff],
-            "gold" => [0xff, 0xd7, 0x00],
-            "goldenrod" => [0xda, 0xa5, 0x20],
-            "gray" => self.gray(),
-            "web gray" => [0x80, 0x80, 0x80],
-            "web green" => [0x00, 0x80, 0x00],
-            "green yellow" => [0xad, 0xff, 0x2f],
-            "honeydew" => [0xf0, 0xff, 0xf0],
-            "hot pink" => [0xff, 0x69, 0xb4],
-            "indian red" => [0xcd, 0x5c, 0x5c],
-            "indigo" => [0x4b, 0x00, 0x82],
-            "ivory" => [0xff, 0xff, 0xf0],
-            "khaki" => [0xf0, 0xe6, 0x8c],
-            "lavender" => [0xe6, 0xe6, 0xfa],
-            "lavender blush" => [0xff, 0xf0, 0xf5],
-            "lawn green" => [0x7c, 0xfc, 0x00],
-            "lemon chiffon" => [0xff, 0xfa, 0xcd],
-            "light blue" => [0xad, 0xd8, 0xe6],
-            "light coral" => [0xf0, 0x80, 0x80],
-            "light cyan" => [0xe0, 0xff, 0xff],
-            "light goldenrod" => [0xfa, 0xfa, 0xd2],
-            "light gray" => [0xd3, 0xd3, 0xd3],
-            "light green" => [0x90, 0xee, 0x90],
-            "light pink" => [0xff, 0xb6, 0xc1],
-            "light salmon" => [0xff, 0xa0, 0x7a],
-            "light sea green" => [0x20, 0xb2, 0xaa],
-            "light sky blue" => [0x87, 0xce, 0xfa],
-            "light slate gray" => [0x77, 0x88, 0x99],
-            "light steel blue" => [0xb0, 0xc4, 0xde],
-            "light yellow" => [0xff, 0xff, 0xe0],
-            "lime" => [0x00, 0xff, 0x00],
-            "lime green" => [0x32, 0xcd, 0x32],
-            "linen" => [0xfa, 0xf0, 0xe6],
-            "maroon" => self.maroon(),
-            "web maroon" => [0x80, 0x00, 0x00],
-            "medium aquamarine" => [0x66, 0xcd, 0xaa],
-            "medium blue" => [0x00, 0x00, 0xcd],
-            "medium orchid" => [0xba, 0x55, 0xd3],
-            "medium purple" => [0x93, 0x70, 0xdb],
-            "medium sea green" => [0x3c, 0xb3, 0x71],
-            "medium slate blue" => [0x7b, 0x68, 0xee],
-            "medium spring green" => [0x00, 0xfa, 0x9a],
-            "medium turquoise" => [0x48, 0xd1, 0xcc],
-            "medium violet red" => [0xc7, 0x15, 0x85],
-            "midnight blue" => [0x19, 0x19, 0x70],
-            "mint cream" => [0xf5, 0xff, 0xfa],
-            "misty rose" => [0xff, 0xe4, 0xe1],
-            "moccasin" => [0xff, 0xe4, 0xb5],
-            "navajo white" => [0xff, 0xde, 0xad],
-            "navy blue" => [0x00, 0x00, 0x80],
-            "old lace" => [0xfd, 0xf5, 0xe6],
-            "olive" => [0x80, 0x80, 0x00],
-            "olive drab" => [0x6b, 0x8e, 0x23],
-            "orange" => [0xff, 0xa5, 0x00],
-            "orange red" => [0xff, 0x45, 0x00],
-            "orchid" => [0xda, 0x70, 0xd6],
-            "pale goldenrod" => [0xee, 0xe8, 0xaa],
-            "pale green" => [0x98, 0xfb, 0x98],
-            "pale turquoise" => [0xaf, 0xee, 0xee],
-            "pale violet red" => [0xdb, 0x70, 0x93],
-            "papaya whip" => [0xff, 0xef, 0xd5],
-            "peach puff" => [0xff, 0xda, 0xb9],
-            "peru" => [0xcd, 0x85, 0x3f],
-            "pink" => [0xff, 0xc0, 0xcb],
-            "plum" => [0xdd, 0xa0, 0xdd],
-            "powder blue" => [0xb0, 0xe0, 0xe6],
-            "purple" => self.purple(),
-            "web purple" => [0x80, 0x00, 0x80],
-            "rebecca purple" => [0x66, 0x33, 0x99],
-            "rosy brown" => [0xbc, 0x8f, 0x8f],
-            "royal blue" => [0x41, 0x69, 0xe1],
-            "saddle brown" => [0x8b, 0x45, 0x13],
-            "salmon" => [0xfa, 0x80, 0x72],
-            "sandy brown" => [0xf4, 0xa4, 0x60],
-            "sea green" => [0x2e, 0x8b, 0x57],
-            "seashell" => [0xff, 0xf5, 0xee],
-            "sienna" => [0xa0, 0x52, 0x2d],
-            "silver" => [0xc0, 0xc0, 0xc0],
-            "sky blue" => [0x87, 0xce, 0xeb],
-            "slate blue" => [0x6a, 0x5a, 0xcd],
-            "slate gray" => [0x70, 0x80, 0x90],
-            "snow" => [0xff, 0xfa, 0xfa],
-            "spring green" => [0x00, 0xff, 0x7f],
-            "steel blue" => [0x46, 0x82, 0xb4],
-            "tan" => [0xd2, 0xb4, 0x8c],
-            "teal" => [0x00, 0x80, 0x80],
-            "thistle" => [0xd8, 0xbf, 0xd8],
-            "tomato" => [0xff, 0x63, 0x47],
-            "turquoise" => [0x40, 0xe0, 0xd0],
-            "violet" => [0xee, 0x82, 0xee],
-            "wheat" => [0xf5, 0xde, 0xb3],
-            "white smoke" => [0xf5, 0xf5, 0xf5],
-            "yellow green" => [0x9a, 0xcd, 0x32],
+        let table: &[(&str, [u8; 3])] = match self {
+            Self::X11 => &X11_COLORS,
+            _ => &CSS_COLORS,
+        };
+        let mut data: HashMap<String, [u8; 3]> = HashMap::new();
+        //normalized name -> the name in data, later names replace earlier ones that only differ
+        //in case or separators so that looking up a name never depends on the hashmap's order
+        let mut normalized: HashMap<String, String> = HashMap::new();
+        let mut insert = |name: &str, clr: [u8; 3]| {
+            if let Some(old) = normalized.insert(normalize_color_name(name), name.to_owned()) {
+                data.remove(&old);
+            }
+            data.insert(name.to_owned(), clr);
         };
+        for (name, clr) in LEGACY_NAMES.iter().chain(table).chain(CLASH_ALIASES.iter()) {
+            insert(name, *clr);
+        }
         for clr_list in low_color_map.keys() {
             for clr in clr_list {
-                data.insert(clr, (low_color_map[clr_list])(self));
+                insert(clr, (low_color_map[clr_list])(self));
             }
         }
         for clr_list in bright_low_color_map.keys() {
             for clr in clr_list {
-                data.insert(clr, (bright_low_color_map[clr_list])(self));
+                insert(clr, (bright_low_color_map[clr_list])(self));
+            }
+        }
+        //the file's colors take priority over the terminal color names
+        if let Self::Custom(path) = self {
+            let mut file_colors: Vec<_> = read_color_name_file(path).into_iter().collect();
+            file_colors.sort();
+            for (name, clr) in file_colors {
+                insert(&name, clr);
             }
         }
         data
     }
-    fn get_color(&self, clr: &str) -> String {
+    ///The `count` named colors closest to clr, closest first
+    ///the numeric ansi aliases (eg: 31, 91) are skipped
+    pub fn nearest_colors(
+        &self,
+        clr: [f64; 3],
+        count: usize,
+        method: &DeltaE,
+    ) -> Vec<(String, [u8; 3], f64)> {
+        let mut distances: Vec<(String, [u8; 3], f64)> = self
+            .list_colors()
+            .iter()
+            .filter(|(name, _)| name.parse::<u8>().is_err())
+            .map(|(name, rgb)| {
+                let named = [rgb[0] as f64, rgb[1] as f64, rgb[2] as f64];
+                (name.to_owned(), *rgb, delta_e(clr, named, method))
+            })
+            .collect();
+        //shorter names first for ties, they're usually the common name (eg: gray vs bright black)
+        distances.sort_by(|a, b| {
+            a.2.total_cmp(&b.2)
+                .then_with(|| a.0.len().cmp(&b.0.len()))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        //only keep one name for colors with aliases (eg: gray/grey, aqua/cyan)
+        let mut seen = vec![];
+        distances.retain(|(_, rgb, _)| {
+            if seen.contains(rgb) {
+                return false;
+            }
+            seen.push(*rgb);
+            true
+        });
+        distances.truncate(count);
+        distances
+    }
+
+    fn get_color(&self, clr: &str) -> Option<String> {
+        let clr = normalize_color_name(clr);
         let clrs = self.list_colors();
-        let clr = clrs.get(&clr).unwrap_or(&[0u8, 0u8, 0u8]);
-        return format!("#{:02x}{:02x}{:02x}", clr[0], clr[1], clr[2]);
+        let (_, clr) = clrs
+            .iter()
+            .find(|(name, _)| normalize_color_name(name) == clr)?;
+        return Some(format!("#{:02x}{:02x}{:02x}", clr[0], clr[1], clr[2]));
     }
 }
 
 //TODO:
 //make it so that each color name standard only implements its own colors, this is more expandable
 
-fn get_ansi_clr_num_with_reader(num: u8) -> [u8; 3] {
-    let mut reader = std::io::stdin();
-    read_ansi_color(&mut reader, num)
+fn terminal_color(num: u8) -> [u8; 3] {
+    let (r, g, b) = hex62rgb(&terminal_palette()[num as usize][1..]);
+    [r, g, b]
 }
 
 impl ColorNameStandard {
-    fn gray(&self) -> [u8; 3] {
-        match self {
-            Self::X11 => [0xbe, 0xbe, 0xbe],
-            _ => [0x80, 0x80, 0x80],
-        }
-    }
     fn black(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0),
+            Self::MyTerm => terminal_color(0),
             _ => [0, 0, 0],
         }
     }
     fn bright_black(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(8),
+            Self::MyTerm => terminal_color(8),
             Self::XTerm => [0x4d, 0x4d, 0x4d],
-            _ => [0xff, 0, 0],
+            _ => [0x80, 0x80, 0x80],
         }
     }
     fn red(&self) -> [u8; 3] {
         match self {
             Self::XTerm => [0xcd, 0, 0],
-            Self::MyTerm => get_ansi_clr_num_with_reader(1),
+            Self::MyTerm => terminal_color(1),
             _ => [0xff, 0, 0],
         }
     }
     fn bright_red(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(9),
+            Self::MyTerm => terminal_color(9),
             _ => [0xff, 0, 0],
         }
     }
@@ -420,135 +906,465 @@ impl ColorNameStandard {
         match self {
             Self::X11 => [0x00, 0xff, 0x00],
             Self::XTerm => [0, 0xcd, 0],
-            Self::MyTerm => get_ansi_clr_num_with_reader(2),
+            Self::MyTerm => terminal_color(2),
             _ => [0, 0x80, 0],
         }
     }
     fn bright_green(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0xA),
+            Self::MyTerm => terminal_color(0xA),
             _ => [0x00, 0xff, 0x00],
         }
     }
     fn yellow(&self) -> [u8; 3] {
         match self {
             Self::XTerm => [0xcd, 0xcd, 0x00],
-            Self::MyTerm => get_ansi_clr_num_with_reader(3),
+            Self::MyTerm => terminal_color(3),
             _ => [0xff, 0xff, 0x00],
         }
     }
     fn blue(&self) -> [u8; 3] {
         match self {
             Self::XTerm => [0x00, 0x00, 0xcd],
-            Self::MyTerm => get_ansi_clr_num_with_reader(4),
+            Self::MyTerm => terminal_color(4),
             _ => [0x00, 0x00, 0xff],
         }
     }
     fn bright_blue(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0xC),
+            Self::MyTerm => terminal_color(0xC),
             _ => [0x00, 0x00, 0xff],
         }
     }
     fn magenta(&self) -> [u8; 3] {
         match self {
             Self::XTerm => [0xcd, 0x00, 0xcd],
-            Self::MyTerm => get_ansi_clr_num_with_reader(5),
+            Self::MyTerm => terminal_color(5),
             _ => [0xff, 0x00, 0xff],
         }
     }
     fn bright_magenta(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0xD),
+            Self::MyTerm => terminal_color(0xD),
             _ => [0xff, 0x00, 0xff],
         }
     }
     fn cyan(&self) -> [u8; 3] {
         match self {
             Self::XTerm => [0x00, 0xcd, 0xcd],
-            Self::MyTerm => get_ansi_clr_num_with_reader(6),
+            Self::MyTerm => terminal_color(6),
             _ => [0x00, 0xff, 0xff],
         }
     }
     fn bright_cyan(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0xE),
+            Self::MyTerm => terminal_color(0xE),
             _ => [0x00, 0xff, 0xff],
         }
     }
     fn white(&self) -> [u8; 3] {
         match self {
             Self::XTerm => [0xe5, 0xe5, 0xe5],
-            Self::MyTerm => get_ansi_clr_num_with_reader(7),
+            Self::MyTerm => terminal_color(7),
             _ => [0xff, 0xff, 0xff],
         }
     }
     fn bright_white(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0xf),
+            Self::MyTerm => terminal_color(0xf),
             _ => [0xff, 0xff, 0xff],
         }
     }
     fn bright_yellow(&self) -> [u8; 3] {
         match self {
-            Self::MyTerm => get_ansi_clr_num_with_reader(0xB),
+            Self::MyTerm => terminal_color(0xB),
             _ => [0xff, 0xff, 0x00],
         }
     }
-    fn maroon(&self) -> [u8; 3] {
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum MixSpace {
+    #[value(name = "rgb")]
+    RGB,
+    #[value(name = "hsl")]
+    HSL,
+    #[value(name = "linear")]
+    LinearRGB,
+    #[value(name = "oklab")]
+    OKLab,
+    #[value(name = "oklch")]
+    OKLCH,
+    #[value(name = "lab")]
+    LAB,
+}
+
+impl MixSpace {
+    pub const ALL: [MixSpace; 6] = [
+        Self::RGB,
+        Self::HSL,
+        Self::LinearRGB,
+        Self::OKLab,
+        Self::OKLCH,
+        Self::LAB,
+    ];
+
+    pub fn next(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::ALL[0]),
+            Some(space) => {
+                let idx = Self::ALL.iter().position(|s| *s == space).unwrap();
+                Self::ALL.get(idx + 1).copied()
+            }
+        }
+    }
+
+    fn interpolation_space(&self) -> InterpolationSpace {
+        match self {
+            Self::RGB => InterpolationSpace::SRGB,
+            Self::HSL => InterpolationSpace::HSL,
+            Self::LinearRGB => InterpolationSpace::LinearRGB,
+            Self::OKLab => InterpolationSpace::OKLab,
+            Self::OKLCH => InterpolationSpace::OKLCH,
+            Self::LAB => InterpolationSpace::LAB,
+        }
+    }
+}
+
+///Mixes percent of clr2 into clr1, both rgba with alpha from 0-1, see interpolate_alpha
+///hues take the shorter way around the color wheel
+pub fn color_mix(
+    clr1: [f64; 4],
+    clr2: [f64; 4],
+    percent: Percentage,
+    mix_space: &MixSpace,
+) -> [f64; 4] {
+    interpolate_alpha(
+        clr1,
+        clr2,
+        percent,
+        &mix_space.interpolation_space(),
+        &HueInterpolation::Shorter,
+    )
+}
+
+///The blend modes from the W3C Compositing and Blending spec
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl BlendMode {
+    ///Blends one channel from 0-1 of the backdrop (cb) and the source (cs)
+    fn blend_channel(&self, cb: f64, cs: f64) -> f64 {
+        let hard_light = |cb: f64, cs: f64| {
+            if cs <= 0.5 {
+                cb * 2.0 * cs
+            } else {
+                let cs = 2.0 * cs - 1.0;
+                cb + cs - cb * cs
+            }
+        };
         match self {
-            Self::X11 => [0xb0, 0x30, 0x60],
-            _ => [0x80, 0x00, 0x00],
+            Self::Multiply => cb * cs,
+            Self::Screen => cb + cs - cb * cs,
+            Self::Overlay => hard_light(cs, cb),
+            Self::Darken => cb.min(cs),
+            Self::Lighten => cb.max(cs),
+            Self::ColorDodge if cb == 0.0 => 0.0,
+            Self::ColorDodge if cs == 1.0 => 1.0,
+            Self::ColorDodge => (cb / (1.0 - cs)).min(1.0),
+            Self::ColorBurn if cb == 1.0 => 1.0,
+            Self::ColorBurn if cs == 0.0 => 0.0,
+            Self::ColorBurn => 1.0 - ((1.0 - cb) / cs).min(1.0),
+            Self::HardLight => hard_light(cb, cs),
+            Self::SoftLight if cs <= 0.5 => cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb),
+            Self::SoftLight => {
+                let d = if cb <= 0.25 {
+                    ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                } else {
+                    cb.sqrt()
+                };
+                cb + (2.0 * cs - 1.0) * (d - cb)
+            }
+            Self::Difference => (cb - cs).abs(),
+            Self::Exclusion => cb + cs - 2.0 * cb * cs,
+            _ => cs,
         }
     }
-    fn purple(&self) -> [u8; 3] {
+
+    ///Blends the backdrop (cb) and the source (cs) with channels from 0-1
+    fn blend(&self, cb: [f64; 3], cs: [f64; 3]) -> [f64; 3] {
         match self {
-            Self::X11 => [0xa0, 0x20, 0xf0],
-            _ => [0x80, 0x00, 0x80],
+            Self::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            Self::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            Self::Color => set_lum(cs, lum(cb)),
+            Self::Luminosity => set_lum(cb, lum(cs)),
+            _ => [0, 1, 2].map(|i| self.blend_channel(cb[i], cs[i])),
         }
     }
 }
 
-pub enum MixSpace {
-    RGB,
+fn lum(c: [f64; 3]) -> f64 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f64; 3]) -> [f64; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut c = c;
+    if n < 0.0 {
+        c = c.map(|channel| l + (channel - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        c = c.map(|channel| l + (channel - l) * (1.0 - l) / (x - l));
+    }
+    c
+}
+
+fn set_lum(c: [f64; 3], l: f64) -> [f64; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|channel| channel + d))
+}
+
+fn sat(c: [f64; 3]) -> f64 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f64; 3], s: f64) -> [f64; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_by(|a, b| c[*a].total_cmp(&c[*b]));
+    let [min, mid, max] = order;
+    let mut out = [0.0; 3];
+    if c[max] > c[min] {
+        out[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        out[max] = s;
+    }
+    out
+}
+
+///Composites the rgba source over the rgba backdrop with a blend mode, alpha from 0-1
+pub fn blend(source: [f64; 4], backdrop: [f64; 4], mode: &BlendMode) -> [f64; 4] {
+    let (a_s, a_b) = (source[3], backdrop[3]);
+    let cs = [source[0], source[1], source[2]].map(|c| c / 255.0);
+    let cb = [backdrop[0], backdrop[1], backdrop[2]].map(|c| c / 255.0);
+    let blended = mode.blend(cb, cs);
+    let alpha = a_s + a_b * (1.0 - a_s);
+    let [r, g, b] = [0, 1, 2].map(|i| {
+        //where the backdrop is transparent the source shows as is
+        let cs = (1.0 - a_b) * cs[i] + a_b * blended[i];
+        let co = a_s * cs + a_b * cb[i] * (1.0 - a_s);
+        if alpha == 0.0 {
+            0.0
+        } else {
+            (clamp!(0.0, co / alpha, 1.0) * 255.0).round()
+        }
+    });
+    [r, g, b, alpha]
+}
+
+///The opaque color an rgba color looks like over an opaque background
+pub fn flatten(clr: [f64; 4], bg: [f64; 3]) -> [f64; 3] {
+    let [r, g, b, _] = blend(clr, [bg[0], bg[1], bg[2], 1.0], &BlendMode::Normal);
+    [r, g, b]
+}
+
+///A space to interpolate colors in, see CSS Color 4's color-interpolation-method
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum InterpolationSpace {
+    #[value(name = "srgb")]
+    SRGB,
+    #[value(name = "linear", alias = "srgb-linear")]
+    LinearRGB,
+    #[value(name = "oklab")]
+    OKLab,
+    #[value(name = "oklch")]
+    OKLCH,
+    #[value(name = "hsl")]
     HSL,
+    #[value(name = "lab")]
+    LAB,
 }
 
-fn color_mix_rgb(clr1: ColorInt, clr2: ColorInt, percent: Percentage) -> ColorInt {
-    let (r1, g1, b1) = number2rgb(clr1);
-    let (r2, g2, b2) = number2rgb(clr2);
-    let clr1_p = 1.0 - percent;
-    return rgb2number(
-        r1 as f64 * clr1_p + r2 as f64 * percent,
-        g1 as f64 * clr1_p + g2 as f64 * percent,
-        b1 as f64 * clr1_p + b2 as f64 * percent,
-    );
+impl InterpolationSpace {
+    ///The name used in css, eg: `in srgb-linear`
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            Self::SRGB => "srgb",
+            Self::LinearRGB => "srgb-linear",
+            Self::OKLab => "oklab",
+            Self::OKLCH => "oklch",
+            Self::HSL => "hsl",
+            Self::LAB => "lab",
+        }
+    }
+
+    ///(index of the hue, index of the saturation/chroma) for polar spaces
+    fn hue_and_chroma_idx(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::HSL => Some((0, 1)),
+            Self::OKLCH => Some((2, 1)),
+            _ => None,
+        }
+    }
+
+    fn coords(&self, clr: [f64; 3]) -> [f64; 3] {
+        let [r, g, b] = clr;
+        let (x, y, z) = match self {
+            Self::SRGB => (r, g, b),
+            Self::LinearRGB => (
+                srgb2linear(r / 255.0),
+                srgb2linear(g / 255.0),
+                srgb2linear(b / 255.0),
+            ),
+            Self::OKLab => rgb2oklab(r, g, b),
+            Self::OKLCH => rgb2oklch(r, g, b),
+            Self::HSL => rgb2hsl(r, g, b),
+            Self::LAB => rgb2lab(r, g, b),
+        };
+        [x, y, z]
+    }
+
+    fn rgb(&self, clr: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = clr;
+        let to_srgb = |c: f64| (clamp!(0.0, linear2srgb(c), 1.0) * 255.0).round();
+        let (r, g, b) = match self {
+            Self::SRGB => (x, y, z),
+            Self::LinearRGB => (to_srgb(x), to_srgb(y), to_srgb(z)),
+            Self::OKLab => oklab2rgb(x, y, z),
+            Self::OKLCH => oklch2rgb_in_gamut(x, y, z.rem_euclid(360.0)),
+            Self::HSL => hsl2rgb(x.rem_euclid(360.0), y, z),
+            Self::LAB => lab2rgb(x, y, z),
+        };
+        [r, g, b]
+    }
+}
+
+///Which way around the hue wheel to go when interpolating hues
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum HueInterpolation {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
 }
 
-fn color_mix_hsl(clr1: ColorInt, clr2: ColorInt, percent: Percentage) -> ColorInt {
-    let (r1, g1, b1) = number2rgb(clr1);
-    let (h1, s1, l1) = rgb2hsl(r1 as f64, g1 as f64, b1 as f64);
-    let (r2, g2, b2) = number2rgb(clr2);
-    let (h2, s2, l2) = rgb2hsl(r2 as f64, g2 as f64, b2 as f64);
-    let clr1_p = 1.0 - percent;
-    let (r, g, b) = hsl2rgb(
-        h1 * clr1_p + h2 * percent,
-        s1 * clr1_p + s2 * percent,
-        l1 * clr1_p + l2 * percent,
+impl HueInterpolation {
+    ///Adjusts 2 hues in degrees so that going from one to the other linearly takes the right arc
+    pub fn fixup(&self, mut h1: f64, mut h2: f64) -> (f64, f64) {
+        let delta = h2 - h1;
+        match self {
+            Self::Shorter if delta > 180.0 => h1 += 360.0,
+            Self::Shorter if delta < -180.0 => h2 += 360.0,
+            Self::Longer if delta > 0.0 && delta < 180.0 => h1 += 360.0,
+            Self::Longer if delta > -180.0 && delta <= 0.0 => h2 += 360.0,
+            Self::Increasing if delta < 0.0 => h2 += 360.0,
+            Self::Decreasing if delta > 0.0 => h1 += 360.0,
+            _ => {}
+        }
+        (h1, h2)
+    }
+}
+
+///Interpolates between 2 rgb colors, t being from 0 (clr1) to 1 (clr2)
+pub fn interpolate(
+    clr1: [f64; 3],
+    clr2: [f64; 3],
+    t: Percentage,
+    space: &InterpolationSpace,
+    hue: &HueInterpolation,
+) -> [f64; 3] {
+    let [r, g, b, _] = interpolate_alpha(
+        [clr1[0], clr1[1], clr1[2], 1.0],
+        [clr2[0], clr2[1], clr2[2], 1.0],
+        t,
+        space,
+        hue,
     );
-    return rgb2number(r, g, b);
+    [r, g, b]
 }
 
-pub fn color_mix(
-    clr1: ColorInt,
-    clr2: ColorInt,
-    percent: Percentage,
-    mix_space: &MixSpace,
-) -> ColorInt {
-    match mix_space {
-        MixSpace::RGB => color_mix_rgb(clr1, clr2, percent),
-        MixSpace::HSL => color_mix_hsl(clr1, clr2, percent),
+///Interpolates between 2 rgba colors with alpha from 0-1
+///The channels are premultiplied by alpha so that a transparent color doesn't drag the mix
+///towards its own color, the hue is never premultiplied
+pub fn interpolate_alpha(
+    clr1: [f64; 4],
+    clr2: [f64; 4],
+    t: Percentage,
+    space: &InterpolationSpace,
+    hue: &HueInterpolation,
+) -> [f64; 4] {
+    let (a1, a2) = (clr1[3], clr2[3]);
+    let mut from = space.coords([clr1[0], clr1[1], clr1[2]]);
+    let mut to = space.coords([clr2[0], clr2[1], clr2[2]]);
+    let mut hue_idx = None;
+    if let Some((h, c)) = space.hue_and_chroma_idx() {
+        //grays have no hue, so they take the hue of the other color instead of going through red
+        if from[c] < 0.0001 {
+            from[h] = to[h];
+        } else if to[c] < 0.0001 {
+            to[h] = from[h];
+        }
+        (from[h], to[h]) = hue.fixup(from[h], to[h]);
+        hue_idx = Some(h);
+    }
+    let alpha = a1 + (a2 - a1) * t;
+    let mut mixed = [0.0; 3];
+    for i in 0..3 {
+        let premultiplied = from[i] * a1 + (to[i] * a2 - from[i] * a1) * t;
+        mixed[i] = if hue_idx == Some(i) || alpha == 0.0 {
+            from[i] + (to[i] - from[i]) * t
+        } else {
+            premultiplied / alpha
+        };
     }
+    let [r, g, b] = space.rgb(mixed);
+    [r, g, b, alpha]
+}
+
+///steps evenly spaced colors going through every color in colors
+pub fn gradient(
+    colors: &[[f64; 3]],
+    steps: usize,
+    space: &InterpolationSpace,
+    hue: &HueInterpolation,
+) -> Vec<[f64; 3]> {
+    if colors.len() < 2 {
+        return colors.iter().cycle().take(steps).copied().collect();
+    }
+    let segments = colors.len() - 1;
+    (0..steps)
+        .map(|i| {
+            let position = match steps {
+                1 => 0.0,
+                _ => i as f64 / (steps - 1) as f64,
+            } * segments as f64;
+            let segment = (position.floor() as usize).min(segments - 1);
+            interpolate(
+                colors[segment],
+                colors[segment + 1],
+                position - segment as f64,
+                space,
+                hue,
+            )
+        })
+        .collect()
 }
 
 pub fn invert(clr: ColorInt) -> ColorInt {
@@ -556,6 +1372,201 @@ pub fn invert(clr: ColorInt) -> ColorInt {
     return rgb2number(255.0 - r as f64, 255.0 - g as f64, 255.0 - b as f64);
 }
 
-pub fn name_to_hex<'a>(name: &str, color_name_standard: &'a ColorNameStandard) -> String {
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum DeltaE {
+    CIE76,
+    CIE94,
+    CIEDE2000,
+    #[value(name = "oklab")]
+    OKLab,
+}
+
+impl DeltaE {
+    pub const ALL: [DeltaE; 4] = [Self::CIE76, Self::CIE94, Self::CIEDE2000, Self::OKLab];
+}
+
+pub fn cie76(lab1: (f64, f64, f64), lab2: (f64, f64, f64)) -> f64 {
+    return ((lab1.0 - lab2.0).powi(2) + (lab1.1 - lab2.1).powi(2) + (lab1.2 - lab2.2).powi(2))
+        .sqrt();
+}
+
+///Uses the graphic arts weights
+pub fn cie94(lab1: (f64, f64, f64), lab2: (f64, f64, f64)) -> f64 {
+    const K1: f64 = 0.045;
+    const K2: f64 = 0.015;
+    let delta_l = lab1.0 - lab2.0;
+    let c1 = (lab1.1 * lab1.1 + lab1.2 * lab1.2).sqrt();
+    let c2 = (lab2.1 * lab2.1 + lab2.2 * lab2.2).sqrt();
+    let delta_c = c1 - c2;
+    let delta_a = lab1.1 - lab2.1;
+    let delta_b = lab1.2 - lab2.2;
+    let delta_h = max!(delta_a * delta_a + delta_b * delta_b - delta_c * delta_c, 0.0).sqrt();
+    let sc = 1.0 + K1 * c1;
+    let sh = 1.0 + K2 * c1;
+    return (delta_l.powi(2) + (delta_c / sc).powi(2) + (delta_h / sh).powi(2)).sqrt();
+}
+
+pub fn ciede2000(lab1: (f64, f64, f64), lab2: (f64, f64, f64)) -> f64 {
+    let (l1, a1, b1) = lab1;
+    let (l2, a2, b2) = lab2;
+    let c_avg = ((a1 * a1 + b1 * b1).sqrt() + (a2 * a2 + b2 * b2).sqrt()) / 2.0;
+    let g = 0.5 * (1.0 - (c_avg.powi(7) / (c_avg.powi(7) + 25f64.powi(7))).sqrt());
+    let a1p = a1 * (1.0 + g);
+    let a2p = a2 * (1.0 + g);
+    let c1p = (a1p * a1p + b1 * b1).sqrt();
+    let c2p = (a2p * a2p + b2 * b2).sqrt();
+    let hue = |b: f64, a: f64| {
+        if b == 0.0 && a == 0.0 {
+            return 0.0;
+        }
+        let h = b.atan2(a).to_degrees();
+        if h < 0.0 {
+            h + 360.0
+        } else {
+            h
+        }
+    };
+    let h1p = hue(b1, a1p);
+    let h2p = hue(b2, a2p);
+
+    let delta_lp = l2 - l1;
+    let delta_cp = c2p - c1p;
+    let mut delta_hp = 0.0;
+    if c1p * c2p != 0.0 {
+        delta_hp = h2p - h1p;
+        if delta_hp > 180.0 {
+            delta_hp -= 360.0;
+        } else if delta_hp < -180.0 {
+            delta_hp += 360.0;
+        }
+    }
+    let delta_big_hp = 2.0 * (c1p * c2p).sqrt() * (delta_hp / 2.0).to_radians().sin();
+
+    let l_avg = (l1 + l2) / 2.0;
+    let cp_avg = (c1p + c2p) / 2.0;
+    let h_avg = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) / 2.0
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) / 2.0
+    } else {
+        (h1p + h2p - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_avg - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_avg).to_radians().cos()
+        + 0.32 * (3.0 * h_avg + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_avg - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((h_avg - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (cp_avg.powi(7) / (cp_avg.powi(7) + 25f64.powi(7))).sqrt();
+    let sl = 1.0 + (0.015 * (l_avg - 50.0).powi(2)) / (20.0 + (l_avg - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * cp_avg;
+    let sh = 1.0 + 0.015 * cp_avg * t;
+    let rt = -(2.0 * delta_theta).to_radians().sin() * rc;
+
+    return ((delta_lp / sl).powi(2)
+        + (delta_cp / sc).powi(2)
+        + (delta_big_hp / sh).powi(2)
+        + rt * (delta_cp / sc) * (delta_big_hp / sh))
+        .sqrt();
+}
+
+///Perceptual difference between 2 rgb colors
+pub fn delta_e(col1: [f64; 3], col2: [f64; 3], method: &DeltaE) -> f64 {
+    if let DeltaE::OKLab = method {
+        let (l1, a1, b1) = rgb2oklab(col1[0], col1[1], col1[2]);
+        let (l2, a2, b2) = rgb2oklab(col2[0], col2[1], col2[2]);
+        return cie76((l1, a1, b1), (l2, a2, b2));
+    }
+    let lab1 = rgb2lab(col1[0], col1[1], col1[2]);
+    let lab2 = rgb2lab(col2[0], col2[1], col2[2]);
+    match method {
+        DeltaE::CIE76 => cie76(lab1, lab2),
+        DeltaE::CIE94 => cie94(lab1, lab2),
+        _ => ciede2000(lab1, lab2),
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum Deficiency {
+    #[value(alias = "protanopia")]
+    Protan,
+    #[value(alias = "deuteranopia")]
+    Deutan,
+    #[value(alias = "tritanopia")]
+    Tritan,
+    #[value(alias = "achromatopsia")]
+    Achroma,
+}
+
+impl Deficiency {
+    ///Machado et al. (2009) matrices for full severity dichromacy, applied in linear rgb
+    fn matrix(&self) -> [[f64; 3]; 3] {
+        match self {
+            Self::Protan => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Self::Deutan => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Self::Tritan => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+            Self::Achroma => [[0.2126, 0.7152, 0.0722]; 3],
+        }
+    }
+
+    ///cycles through every deficiency, None being no simulation
+    pub fn next(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Protan),
+            Some(Self::Protan) => Some(Self::Deutan),
+            Some(Self::Deutan) => Some(Self::Tritan),
+            Some(Self::Tritan) => Some(Self::Achroma),
+            Some(Self::Achroma) => None,
+        }
+    }
+}
+
+///Parses a deficiency severity, which has to be from 0 to 1
+pub fn parse_severity(severity: &str) -> Result<Percentage, String> {
+    match severity.parse::<Percentage>() {
+        Ok(severity) if (0.0..=1.0).contains(&severity) => Ok(severity),
+        _ => Err(format!("Invalid severity: {}, expected a number from 0 to 1", severity)),
+    }
+}
+
+///Simulates how clr looks with a color vision deficiency
+///severity below 1 (anomalous trichromacy) blends between normal vision and the full deficiency,
+///it is clamped to 0-1 since the matrices can't be extrapolated
+pub fn simulate_cvd(clr: ColorInt, deficiency: &Deficiency, severity: Percentage) -> ColorInt {
+    let severity = clamp!(0.0, severity, 1.0);
+    let (r, g, b) = number2rgb(clr);
+    let linear = [
+        srgb2linear(r as f64 / 255.0),
+        srgb2linear(g as f64 / 255.0),
+        srgb2linear(b as f64 / 255.0),
+    ];
+    let matrix = deficiency.matrix();
+    let mut result = [0.0; 3];
+    for i in 0..3 {
+        let simulated: f64 = (0..3).map(|j| matrix[i][j] * linear[j]).sum();
+        let mixed = linear[i] * (1.0 - severity) + simulated * severity;
+        result[i] = (linear2srgb(clamp!(0.0, mixed, 1.0)) * 255.0).round();
+    }
+    return rgb2number(result[0], result[1], result[2]);
+}
+
+pub fn name_to_hex<'a>(name: &str, color_name_standard: &'a ColorNameStandard) -> Option<String> {
+    if let Some(clr) = user_colors().and_then(|clrs| clrs.get(&normalize_color_name(name))) {
+        return Some(format!("#{:02x}{:02x}{:02x}", clr[0], clr[1], clr[2]));
+    }
     return color_name_standard.get_color(name);
 }
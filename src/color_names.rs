@@ -0,0 +1,843 @@
+//named color tables, see https://www.w3.org/TR/css-color-4/#named-colors and the X.Org rgb.txt
+
+///Every named color in CSS Color Module Level 4
+pub const CSS_COLORS: [(&str, [u8; 3]); 148] = [
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
+///Every color in the X11 rgb.txt, names are lowercase and separated by spaces
+pub const X11_COLORS: [(&str, [u8; 3]); 657] = [
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("ghost white", [0xf8, 0xf8, 0xff]),
+    ("white smoke", [0xf5, 0xf5, 0xf5]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("floral white", [0xff, 0xfa, 0xf0]),
+    ("old lace", [0xfd, 0xf5, 0xe6]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("antique white", [0xfa, 0xeb, 0xd7]),
+    ("papaya whip", [0xff, 0xef, 0xd5]),
+    ("blanched almond", [0xff, 0xeb, 0xcd]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("peach puff", [0xff, 0xda, 0xb9]),
+    ("navajo white", [0xff, 0xde, 0xad]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("lemon chiffon", [0xff, 0xfa, 0xcd]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("mint cream", [0xf5, 0xff, 0xfa]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("alice blue", [0xf0, 0xf8, 0xff]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavender blush", [0xff, 0xf0, 0xf5]),
+    ("misty rose", [0xff, 0xe4, 0xe1]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("dark slate gray", [0x2f, 0x4f, 0x4f]),
+    ("dark slate grey", [0x2f, 0x4f, 0x4f]),
+    ("dim gray", [0x69, 0x69, 0x69]),
+    ("dim grey", [0x69, 0x69, 0x69]),
+    ("slate gray", [0x70, 0x80, 0x90]),
+    ("slate grey", [0x70, 0x80, 0x90]),
+    ("light slate gray", [0x77, 0x88, 0x99]),
+    ("light slate grey", [0x77, 0x88, 0x99]),
+    ("gray", [0xbe, 0xbe, 0xbe]),
+    ("grey", [0xbe, 0xbe, 0xbe]),
+    ("light grey", [0xd3, 0xd3, 0xd3]),
+    ("light gray", [0xd3, 0xd3, 0xd3]),
+    ("midnight blue", [0x19, 0x19, 0x70]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("navy blue", [0x00, 0x00, 0x80]),
+    ("cornflower blue", [0x64, 0x95, 0xed]),
+    ("dark slate blue", [0x48, 0x3d, 0x8b]),
+    ("slate blue", [0x6a, 0x5a, 0xcd]),
+    ("medium slate blue", [0x7b, 0x68, 0xee]),
+    ("light slate blue", [0x84, 0x70, 0xff]),
+    ("medium blue", [0x00, 0x00, 0xcd]),
+    ("royal blue", [0x41, 0x69, 0xe1]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("dodger blue", [0x1e, 0x90, 0xff]),
+    ("deep sky blue", [0x00, 0xbf, 0xff]),
+    ("sky blue", [0x87, 0xce, 0xeb]),
+    ("light sky blue", [0x87, 0xce, 0xfa]),
+    ("steel blue", [0x46, 0x82, 0xb4]),
+    ("light steel blue", [0xb0, 0xc4, 0xde]),
+    ("light blue", [0xad, 0xd8, 0xe6]),
+    ("powder blue", [0xb0, 0xe0, 0xe6]),
+    ("pale turquoise", [0xaf, 0xee, 0xee]),
+    ("dark turquoise", [0x00, 0xce, 0xd1]),
+    ("medium turquoise", [0x48, 0xd1, 0xcc]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("light cyan", [0xe0, 0xff, 0xff]),
+    ("cadet blue", [0x5f, 0x9e, 0xa0]),
+    ("medium aquamarine", [0x66, 0xcd, 0xaa]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("dark green", [0x00, 0x64, 0x00]),
+    ("dark olive green", [0x55, 0x6b, 0x2f]),
+    ("dark sea green", [0x8f, 0xbc, 0x8f]),
+    ("sea green", [0x2e, 0x8b, 0x57]),
+    ("medium sea green", [0x3c, 0xb3, 0x71]),
+    ("light sea green", [0x20, 0xb2, 0xaa]),
+    ("pale green", [0x98, 0xfb, 0x98]),
+    ("spring green", [0x00, 0xff, 0x7f]),
+    ("lawn green", [0x7c, 0xfc, 0x00]),
+    ("green", [0x00, 0xff, 0x00]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("medium spring green", [0x00, 0xfa, 0x9a]),
+    ("green yellow", [0xad, 0xff, 0x2f]),
+    ("lime green", [0x32, 0xcd, 0x32]),
+    ("yellow green", [0x9a, 0xcd, 0x32]),
+    ("forest green", [0x22, 0x8b, 0x22]),
+    ("olive drab", [0x6b, 0x8e, 0x23]),
+    ("dark khaki", [0xbd, 0xb7, 0x6b]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("pale goldenrod", [0xee, 0xe8, 0xaa]),
+    ("light goldenrod yellow", [0xfa, 0xfa, 0xd2]),
+    ("light yellow", [0xff, 0xff, 0xe0]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("light goldenrod", [0xee, 0xdd, 0x82]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("dark goldenrod", [0xb8, 0x86, 0x0b]),
+    ("rosy brown", [0xbc, 0x8f, 0x8f]),
+    ("indian red", [0xcd, 0x5c, 0x5c]),
+    ("saddle brown", [0x8b, 0x45, 0x13]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("sandy brown", [0xf4, 0xa4, 0x60]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("dark salmon", [0xe9, 0x96, 0x7a]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("light salmon", [0xff, 0xa0, 0x7a]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("dark orange", [0xff, 0x8c, 0x00]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("light coral", [0xf0, 0x80, 0x80]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("orange red", [0xff, 0x45, 0x00]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("hot pink", [0xff, 0x69, 0xb4]),
+    ("deep pink", [0xff, 0x14, 0x93]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("light pink", [0xff, 0xb6, 0xc1]),
+    ("pale violet red", [0xdb, 0x70, 0x93]),
+    ("maroon", [0xb0, 0x30, 0x60]),
+    ("medium violet red", [0xc7, 0x15, 0x85]),
+    ("violet red", [0xd0, 0x20, 0x90]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("medium orchid", [0xba, 0x55, 0xd3]),
+    ("dark orchid", [0x99, 0x32, 0xcc]),
+    ("dark violet", [0x94, 0x00, 0xd3]),
+    ("blue violet", [0x8a, 0x2b, 0xe2]),
+    ("purple", [0xa0, 0x20, 0xf0]),
+    ("medium purple", [0x93, 0x70, 0xdb]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("snow1", [0xff, 0xfa, 0xfa]),
+    ("snow2", [0xee, 0xe9, 0xe9]),
+    ("snow3", [0xcd, 0xc9, 0xc9]),
+    ("snow4", [0x8b, 0x89, 0x89]),
+    ("seashell1", [0xff, 0xf5, 0xee]),
+    ("seashell2", [0xee, 0xe5, 0xde]),
+    ("seashell3", [0xcd, 0xc5, 0xbf]),
+    ("seashell4", [0x8b, 0x86, 0x82]),
+    ("antique white1", [0xff, 0xef, 0xdb]),
+    ("antique white2", [0xee, 0xdf, 0xcc]),
+    ("antique white3", [0xcd, 0xc0, 0xb0]),
+    ("antique white4", [0x8b, 0x83, 0x78]),
+    ("bisque1", [0xff, 0xe4, 0xc4]),
+    ("bisque2", [0xee, 0xd5, 0xb7]),
+    ("bisque3", [0xcd, 0xb7, 0x9e]),
+    ("bisque4", [0x8b, 0x7d, 0x6b]),
+    ("peach puff1", [0xff, 0xda, 0xb9]),
+    ("peach puff2", [0xee, 0xcb, 0xad]),
+    ("peach puff3", [0xcd, 0xaf, 0x95]),
+    ("peach puff4", [0x8b, 0x77, 0x65]),
+    ("navajo white1", [0xff, 0xde, 0xad]),
+    ("navajo white2", [0xee, 0xcf, 0xa1]),
+    ("navajo white3", [0xcd, 0xb3, 0x8b]),
+    ("navajo white4", [0x8b, 0x79, 0x5e]),
+    ("lemon chiffon1", [0xff, 0xfa, 0xcd]),
+    ("lemon chiffon2", [0xee, 0xe9, 0xbf]),
+    ("lemon chiffon3", [0xcd, 0xc9, 0xa5]),
+    ("lemon chiffon4", [0x8b, 0x89, 0x70]),
+    ("cornsilk1", [0xff, 0xf8, 0xdc]),
+    ("cornsilk2", [0xee, 0xe8, 0xcd]),
+    ("cornsilk3", [0xcd, 0xc8, 0xb1]),
+    ("cornsilk4", [0x8b, 0x88, 0x78]),
+    ("ivory1", [0xff, 0xff, 0xf0]),
+    ("ivory2", [0xee, 0xee, 0xe0]),
+    ("ivory3", [0xcd, 0xcd, 0xc1]),
+    ("ivory4", [0x8b, 0x8b, 0x83]),
+    ("honeydew1", [0xf0, 0xff, 0xf0]),
+    ("honeydew2", [0xe0, 0xee, 0xe0]),
+    ("honeydew3", [0xc1, 0xcd, 0xc1]),
+    ("honeydew4", [0x83, 0x8b, 0x83]),
+    ("lavender blush1", [0xff, 0xf0, 0xf5]),
+    ("lavender blush2", [0xee, 0xe0, 0xe5]),
+    ("lavender blush3", [0xcd, 0xc1, 0xc5]),
+    ("lavender blush4", [0x8b, 0x83, 0x86]),
+    ("misty rose1", [0xff, 0xe4, 0xe1]),
+    ("misty rose2", [0xee, 0xd5, 0xd2]),
+    ("misty rose3", [0xcd, 0xb7, 0xb5]),
+    ("misty rose4", [0x8b, 0x7d, 0x7b]),
+    ("azure1", [0xf0, 0xff, 0xff]),
+    ("azure2", [0xe0, 0xee, 0xee]),
+    ("azure3", [0xc1, 0xcd, 0xcd]),
+    ("azure4", [0x83, 0x8b, 0x8b]),
+    ("slate blue1", [0x83, 0x6f, 0xff]),
+    ("slate blue2", [0x7a, 0x67, 0xee]),
+    ("slate blue3", [0x69, 0x59, 0xcd]),
+    ("slate blue4", [0x47, 0x3c, 0x8b]),
+    ("royal blue1", [0x48, 0x76, 0xff]),
+    ("royal blue2", [0x43, 0x6e, 0xee]),
+    ("royal blue3", [0x3a, 0x5f, 0xcd]),
+    ("royal blue4", [0x27, 0x40, 0x8b]),
+    ("blue1", [0x00, 0x00, 0xff]),
+    ("blue2", [0x00, 0x00, 0xee]),
+    ("blue3", [0x00, 0x00, 0xcd]),
+    ("blue4", [0x00, 0x00, 0x8b]),
+    ("dodger blue1", [0x1e, 0x90, 0xff]),
+    ("dodger blue2", [0x1c, 0x86, 0xee]),
+    ("dodger blue3", [0x18, 0x74, 0xcd]),
+    ("dodger blue4", [0x10, 0x4e, 0x8b]),
+    ("steel blue1", [0x63, 0xb8, 0xff]),
+    ("steel blue2", [0x5c, 0xac, 0xee]),
+    ("steel blue3", [0x4f, 0x94, 0xcd]),
+    ("steel blue4", [0x36, 0x64, 0x8b]),
+    ("deep sky blue1", [0x00, 0xbf, 0xff]),
+    ("deep sky blue2", [0x00, 0xb2, 0xee]),
+    ("deep sky blue3", [0x00, 0x9a, 0xcd]),
+    ("deep sky blue4", [0x00, 0x68, 0x8b]),
+    ("sky blue1", [0x87, 0xce, 0xff]),
+    ("sky blue2", [0x7e, 0xc0, 0xee]),
+    ("sky blue3", [0x6c, 0xa6, 0xcd]),
+    ("sky blue4", [0x4a, 0x70, 0x8b]),
+    ("light sky blue1", [0xb0, 0xe2, 0xff]),
+    ("light sky blue2", [0xa4, 0xd3, 0xee]),
+    ("light sky blue3", [0x8d, 0xb6, 0xcd]),
+    ("light sky blue4", [0x60, 0x7b, 0x8b]),
+    ("slate gray1", [0xc6, 0xe2, 0xff]),
+    ("slate gray2", [0xb9, 0xd3, 0xee]),
+    ("slate gray3", [0x9f, 0xb6, 0xcd]),
+    ("slate gray4", [0x6c, 0x7b, 0x8b]),
+    ("light steel blue1", [0xca, 0xe1, 0xff]),
+    ("light steel blue2", [0xbc, 0xd2, 0xee]),
+    ("light steel blue3", [0xa2, 0xb5, 0xcd]),
+    ("light steel blue4", [0x6e, 0x7b, 0x8b]),
+    ("light blue1", [0xbf, 0xef, 0xff]),
+    ("light blue2", [0xb2, 0xdf, 0xee]),
+    ("light blue3", [0x9a, 0xc0, 0xcd]),
+    ("light blue4", [0x68, 0x83, 0x8b]),
+    ("light cyan1", [0xe0, 0xff, 0xff]),
+    ("light cyan2", [0xd1, 0xee, 0xee]),
+    ("light cyan3", [0xb4, 0xcd, 0xcd]),
+    ("light cyan4", [0x7a, 0x8b, 0x8b]),
+    ("pale turquoise1", [0xbb, 0xff, 0xff]),
+    ("pale turquoise2", [0xae, 0xee, 0xee]),
+    ("pale turquoise3", [0x96, 0xcd, 0xcd]),
+    ("pale turquoise4", [0x66, 0x8b, 0x8b]),
+    ("cadet blue1", [0x98, 0xf5, 0xff]),
+    ("cadet blue2", [0x8e, 0xe5, 0xee]),
+    ("cadet blue3", [0x7a, 0xc5, 0xcd]),
+    ("cadet blue4", [0x53, 0x86, 0x8b]),
+    ("turquoise1", [0x00, 0xf5, 0xff]),
+    ("turquoise2", [0x00, 0xe5, 0xee]),
+    ("turquoise3", [0x00, 0xc5, 0xcd]),
+    ("turquoise4", [0x00, 0x86, 0x8b]),
+    ("cyan1", [0x00, 0xff, 0xff]),
+    ("cyan2", [0x00, 0xee, 0xee]),
+    ("cyan3", [0x00, 0xcd, 0xcd]),
+    ("cyan4", [0x00, 0x8b, 0x8b]),
+    ("dark slate gray1", [0x97, 0xff, 0xff]),
+    ("dark slate gray2", [0x8d, 0xee, 0xee]),
+    ("dark slate gray3", [0x79, 0xcd, 0xcd]),
+    ("dark slate gray4", [0x52, 0x8b, 0x8b]),
+    ("aquamarine1", [0x7f, 0xff, 0xd4]),
+    ("aquamarine2", [0x76, 0xee, 0xc6]),
+    ("aquamarine3", [0x66, 0xcd, 0xaa]),
+    ("aquamarine4", [0x45, 0x8b, 0x74]),
+    ("dark sea green1", [0xc1, 0xff, 0xc1]),
+    ("dark sea green2", [0xb4, 0xee, 0xb4]),
+    ("dark sea green3", [0x9b, 0xcd, 0x9b]),
+    ("dark sea green4", [0x69, 0x8b, 0x69]),
+    ("sea green1", [0x54, 0xff, 0x9f]),
+    ("sea green2", [0x4e, 0xee, 0x94]),
+    ("sea green3", [0x43, 0xcd, 0x80]),
+    ("sea green4", [0x2e, 0x8b, 0x57]),
+    ("pale green1", [0x9a, 0xff, 0x9a]),
+    ("pale green2", [0x90, 0xee, 0x90]),
+    ("pale green3", [0x7c, 0xcd, 0x7c]),
+    ("pale green4", [0x54, 0x8b, 0x54]),
+    ("spring green1", [0x00, 0xff, 0x7f]),
+    ("spring green2", [0x00, 0xee, 0x76]),
+    ("spring green3", [0x00, 0xcd, 0x66]),
+    ("spring green4", [0x00, 0x8b, 0x45]),
+    ("green1", [0x00, 0xff, 0x00]),
+    ("green2", [0x00, 0xee, 0x00]),
+    ("green3", [0x00, 0xcd, 0x00]),
+    ("green4", [0x00, 0x8b, 0x00]),
+    ("chartreuse1", [0x7f, 0xff, 0x00]),
+    ("chartreuse2", [0x76, 0xee, 0x00]),
+    ("chartreuse3", [0x66, 0xcd, 0x00]),
+    ("chartreuse4", [0x45, 0x8b, 0x00]),
+    ("olive drab1", [0xc0, 0xff, 0x3e]),
+    ("olive drab2", [0xb3, 0xee, 0x3a]),
+    ("olive drab3", [0x9a, 0xcd, 0x32]),
+    ("olive drab4", [0x69, 0x8b, 0x22]),
+    ("dark olive green1", [0xca, 0xff, 0x70]),
+    ("dark olive green2", [0xbc, 0xee, 0x68]),
+    ("dark olive green3", [0xa2, 0xcd, 0x5a]),
+    ("dark olive green4", [0x6e, 0x8b, 0x3d]),
+    ("khaki1", [0xff, 0xf6, 0x8f]),
+    ("khaki2", [0xee, 0xe6, 0x85]),
+    ("khaki3", [0xcd, 0xc6, 0x73]),
+    ("khaki4", [0x8b, 0x86, 0x4e]),
+    ("light goldenrod1", [0xff, 0xec, 0x8b]),
+    ("light goldenrod2", [0xee, 0xdc, 0x82]),
+    ("light goldenrod3", [0xcd, 0xbe, 0x70]),
+    ("light goldenrod4", [0x8b, 0x81, 0x4c]),
+    ("light yellow1", [0xff, 0xff, 0xe0]),
+    ("light yellow2", [0xee, 0xee, 0xd1]),
+    ("light yellow3", [0xcd, 0xcd, 0xb4]),
+    ("light yellow4", [0x8b, 0x8b, 0x7a]),
+    ("yellow1", [0xff, 0xff, 0x00]),
+    ("yellow2", [0xee, 0xee, 0x00]),
+    ("yellow3", [0xcd, 0xcd, 0x00]),
+    ("yellow4", [0x8b, 0x8b, 0x00]),
+    ("gold1", [0xff, 0xd7, 0x00]),
+    ("gold2", [0xee, 0xc9, 0x00]),
+    ("gold3", [0xcd, 0xad, 0x00]),
+    ("gold4", [0x8b, 0x75, 0x00]),
+    ("goldenrod1", [0xff, 0xc1, 0x25]),
+    ("goldenrod2", [0xee, 0xb4, 0x22]),
+    ("goldenrod3", [0xcd, 0x9b, 0x1d]),
+    ("goldenrod4", [0x8b, 0x69, 0x14]),
+    ("dark goldenrod1", [0xff, 0xb9, 0x0f]),
+    ("dark goldenrod2", [0xee, 0xad, 0x0e]),
+    ("dark goldenrod3", [0xcd, 0x95, 0x0c]),
+    ("dark goldenrod4", [0x8b, 0x65, 0x08]),
+    ("rosy brown1", [0xff, 0xc1, 0xc1]),
+    ("rosy brown2", [0xee, 0xb4, 0xb4]),
+    ("rosy brown3", [0xcd, 0x9b, 0x9b]),
+    ("rosy brown4", [0x8b, 0x69, 0x69]),
+    ("indian red1", [0xff, 0x6a, 0x6a]),
+    ("indian red2", [0xee, 0x63, 0x63]),
+    ("indian red3", [0xcd, 0x55, 0x55]),
+    ("indian red4", [0x8b, 0x3a, 0x3a]),
+    ("sienna1", [0xff, 0x82, 0x47]),
+    ("sienna2", [0xee, 0x79, 0x42]),
+    ("sienna3", [0xcd, 0x68, 0x39]),
+    ("sienna4", [0x8b, 0x47, 0x26]),
+    ("burlywood1", [0xff, 0xd3, 0x9b]),
+    ("burlywood2", [0xee, 0xc5, 0x91]),
+    ("burlywood3", [0xcd, 0xaa, 0x7d]),
+    ("burlywood4", [0x8b, 0x73, 0x55]),
+    ("wheat1", [0xff, 0xe7, 0xba]),
+    ("wheat2", [0xee, 0xd8, 0xae]),
+    ("wheat3", [0xcd, 0xba, 0x96]),
+    ("wheat4", [0x8b, 0x7e, 0x66]),
+    ("tan1", [0xff, 0xa5, 0x4f]),
+    ("tan2", [0xee, 0x9a, 0x49]),
+    ("tan3", [0xcd, 0x85, 0x3f]),
+    ("tan4", [0x8b, 0x5a, 0x2b]),
+    ("chocolate1", [0xff, 0x7f, 0x24]),
+    ("chocolate2", [0xee, 0x76, 0x21]),
+    ("chocolate3", [0xcd, 0x66, 0x1d]),
+    ("chocolate4", [0x8b, 0x45, 0x13]),
+    ("firebrick1", [0xff, 0x30, 0x30]),
+    ("firebrick2", [0xee, 0x2c, 0x2c]),
+    ("firebrick3", [0xcd, 0x26, 0x26]),
+    ("firebrick4", [0x8b, 0x1a, 0x1a]),
+    ("brown1", [0xff, 0x40, 0x40]),
+    ("brown2", [0xee, 0x3b, 0x3b]),
+    ("brown3", [0xcd, 0x33, 0x33]),
+    ("brown4", [0x8b, 0x23, 0x23]),
+    ("salmon1", [0xff, 0x8c, 0x69]),
+    ("salmon2", [0xee, 0x82, 0x62]),
+    ("salmon3", [0xcd, 0x70, 0x54]),
+    ("salmon4", [0x8b, 0x4c, 0x39]),
+    ("light salmon1", [0xff, 0xa0, 0x7a]),
+    ("light salmon2", [0xee, 0x95, 0x72]),
+    ("light salmon3", [0xcd, 0x81, 0x62]),
+    ("light salmon4", [0x8b, 0x57, 0x42]),
+    ("orange1", [0xff, 0xa5, 0x00]),
+    ("orange2", [0xee, 0x9a, 0x00]),
+    ("orange3", [0xcd, 0x85, 0x00]),
+    ("orange4", [0x8b, 0x5a, 0x00]),
+    ("dark orange1", [0xff, 0x7f, 0x00]),
+    ("dark orange2", [0xee, 0x76, 0x00]),
+    ("dark orange3", [0xcd, 0x66, 0x00]),
+    ("dark orange4", [0x8b, 0x45, 0x00]),
+    ("coral1", [0xff, 0x72, 0x56]),
+    ("coral2", [0xee, 0x6a, 0x50]),
+    ("coral3", [0xcd, 0x5b, 0x45]),
+    ("coral4", [0x8b, 0x3e, 0x2f]),
+    ("tomato1", [0xff, 0x63, 0x47]),
+    ("tomato2", [0xee, 0x5c, 0x42]),
+    ("tomato3", [0xcd, 0x4f, 0x39]),
+    ("tomato4", [0x8b, 0x36, 0x26]),
+    ("orange red1", [0xff, 0x45, 0x00]),
+    ("orange red2", [0xee, 0x40, 0x00]),
+    ("orange red3", [0xcd, 0x37, 0x00]),
+    ("orange red4", [0x8b, 0x25, 0x00]),
+    ("red1", [0xff, 0x00, 0x00]),
+    ("red2", [0xee, 0x00, 0x00]),
+    ("red3", [0xcd, 0x00, 0x00]),
+    ("red4", [0x8b, 0x00, 0x00]),
+    ("deep pink1", [0xff, 0x14, 0x93]),
+    ("deep pink2", [0xee, 0x12, 0x89]),
+    ("deep pink3", [0xcd, 0x10, 0x76]),
+    ("deep pink4", [0x8b, 0x0a, 0x50]),
+    ("hot pink1", [0xff, 0x6e, 0xb4]),
+    ("hot pink2", [0xee, 0x6a, 0xa7]),
+    ("hot pink3", [0xcd, 0x60, 0x90]),
+    ("hot pink4", [0x8b, 0x3a, 0x62]),
+    ("pink1", [0xff, 0xb5, 0xc5]),
+    ("pink2", [0xee, 0xa9, 0xb8]),
+    ("pink3", [0xcd, 0x91, 0x9e]),
+    ("pink4", [0x8b, 0x63, 0x6c]),
+    ("light pink1", [0xff, 0xae, 0xb9]),
+    ("light pink2", [0xee, 0xa2, 0xad]),
+    ("light pink3", [0xcd, 0x8c, 0x95]),
+    ("light pink4", [0x8b, 0x5f, 0x65]),
+    ("pale violet red1", [0xff, 0x82, 0xab]),
+    ("pale violet red2", [0xee, 0x79, 0x9f]),
+    ("pale violet red3", [0xcd, 0x68, 0x89]),
+    ("pale violet red4", [0x8b, 0x47, 0x5d]),
+    ("maroon1", [0xff, 0x34, 0xb3]),
+    ("maroon2", [0xee, 0x30, 0xa7]),
+    ("maroon3", [0xcd, 0x29, 0x90]),
+    ("maroon4", [0x8b, 0x1c, 0x62]),
+    ("violet red1", [0xff, 0x3e, 0x96]),
+    ("violet red2", [0xee, 0x3a, 0x8c]),
+    ("violet red3", [0xcd, 0x32, 0x78]),
+    ("violet red4", [0x8b, 0x22, 0x52]),
+    ("magenta1", [0xff, 0x00, 0xff]),
+    ("magenta2", [0xee, 0x00, 0xee]),
+    ("magenta3", [0xcd, 0x00, 0xcd]),
+    ("magenta4", [0x8b, 0x00, 0x8b]),
+    ("orchid1", [0xff, 0x83, 0xfa]),
+    ("orchid2", [0xee, 0x7a, 0xe9]),
+    ("orchid3", [0xcd, 0x69, 0xc9]),
+    ("orchid4", [0x8b, 0x47, 0x89]),
+    ("plum1", [0xff, 0xbb, 0xff]),
+    ("plum2", [0xee, 0xae, 0xee]),
+    ("plum3", [0xcd, 0x96, 0xcd]),
+    ("plum4", [0x8b, 0x66, 0x8b]),
+    ("medium orchid1", [0xe0, 0x66, 0xff]),
+    ("medium orchid2", [0xd1, 0x5f, 0xee]),
+    ("medium orchid3", [0xb4, 0x52, 0xcd]),
+    ("medium orchid4", [0x7a, 0x37, 0x8b]),
+    ("dark orchid1", [0xbf, 0x3e, 0xff]),
+    ("dark orchid2", [0xb2, 0x3a, 0xee]),
+    ("dark orchid3", [0x9a, 0x32, 0xcd]),
+    ("dark orchid4", [0x68, 0x22, 0x8b]),
+    ("purple1", [0x9b, 0x30, 0xff]),
+    ("purple2", [0x91, 0x2c, 0xee]),
+    ("purple3", [0x7d, 0x26, 0xcd]),
+    ("purple4", [0x55, 0x1a, 0x8b]),
+    ("medium purple1", [0xab, 0x82, 0xff]),
+    ("medium purple2", [0x9f, 0x79, 0xee]),
+    ("medium purple3", [0x89, 0x68, 0xcd]),
+    ("medium purple4", [0x5d, 0x47, 0x8b]),
+    ("thistle1", [0xff, 0xe1, 0xff]),
+    ("thistle2", [0xee, 0xd2, 0xee]),
+    ("thistle3", [0xcd, 0xb5, 0xcd]),
+    ("thistle4", [0x8b, 0x7b, 0x8b]),
+    ("gray0", [0x00, 0x00, 0x00]),
+    ("grey0", [0x00, 0x00, 0x00]),
+    ("gray1", [0x03, 0x03, 0x03]),
+    ("grey1", [0x03, 0x03, 0x03]),
+    ("gray2", [0x05, 0x05, 0x05]),
+    ("grey2", [0x05, 0x05, 0x05]),
+    ("gray3", [0x08, 0x08, 0x08]),
+    ("grey3", [0x08, 0x08, 0x08]),
+    ("gray4", [0x0a, 0x0a, 0x0a]),
+    ("grey4", [0x0a, 0x0a, 0x0a]),
+    ("gray5", [0x0d, 0x0d, 0x0d]),
+    ("grey5", [0x0d, 0x0d, 0x0d]),
+    ("gray6", [0x0f, 0x0f, 0x0f]),
+    ("grey6", [0x0f, 0x0f, 0x0f]),
+    ("gray7", [0x12, 0x12, 0x12]),
+    ("grey7", [0x12, 0x12, 0x12]),
+    ("gray8", [0x14, 0x14, 0x14]),
+    ("grey8", [0x14, 0x14, 0x14]),
+    ("gray9", [0x17, 0x17, 0x17]),
+    ("grey9", [0x17, 0x17, 0x17]),
+    ("gray10", [0x1a, 0x1a, 0x1a]),
+    ("grey10", [0x1a, 0x1a, 0x1a]),
+    ("gray11", [0x1c, 0x1c, 0x1c]),
+    ("grey11", [0x1c, 0x1c, 0x1c]),
+    ("gray12", [0x1f, 0x1f, 0x1f]),
+    ("grey12", [0x1f, 0x1f, 0x1f]),
+    ("gray13", [0x21, 0x21, 0x21]),
+    ("grey13", [0x21, 0x21, 0x21]),
+    ("gray14", [0x24, 0x24, 0x24]),
+    ("grey14", [0x24, 0x24, 0x24]),
+    ("gray15", [0x26, 0x26, 0x26]),
+    ("grey15", [0x26, 0x26, 0x26]),
+    ("gray16", [0x29, 0x29, 0x29]),
+    ("grey16", [0x29, 0x29, 0x29]),
+    ("gray17", [0x2b, 0x2b, 0x2b]),
+    ("grey17", [0x2b, 0x2b, 0x2b]),
+    ("gray18", [0x2e, 0x2e, 0x2e]),
+    ("grey18", [0x2e, 0x2e, 0x2e]),
+    ("gray19", [0x30, 0x30, 0x30]),
+    ("grey19", [0x30, 0x30, 0x30]),
+    ("gray20", [0x33, 0x33, 0x33]),
+    ("grey20", [0x33, 0x33, 0x33]),
+    ("gray21", [0x36, 0x36, 0x36]),
+    ("grey21", [0x36, 0x36, 0x36]),
+    ("gray22", [0x38, 0x38, 0x38]),
+    ("grey22", [0x38, 0x38, 0x38]),
+    ("gray23", [0x3b, 0x3b, 0x3b]),
+    ("grey23", [0x3b, 0x3b, 0x3b]),
+    ("gray24", [0x3d, 0x3d, 0x3d]),
+    ("grey24", [0x3d, 0x3d, 0x3d]),
+    ("gray25", [0x40, 0x40, 0x40]),
+    ("grey25", [0x40, 0x40, 0x40]),
+    ("gray26", [0x42, 0x42, 0x42]),
+    ("grey26", [0x42, 0x42, 0x42]),
+    ("gray27", [0x45, 0x45, 0x45]),
+    ("grey27", [0x45, 0x45, 0x45]),
+    ("gray28", [0x47, 0x47, 0x47]),
+    ("grey28", [0x47, 0x47, 0x47]),
+    ("gray29", [0x4a, 0x4a, 0x4a]),
+    ("grey29", [0x4a, 0x4a, 0x4a]),
+    ("gray30", [0x4d, 0x4d, 0x4d]),
+    ("grey30", [0x4d, 0x4d, 0x4d]),
+    ("gray31", [0x4f, 0x4f, 0x4f]),
+    ("grey31", [0x4f, 0x4f, 0x4f]),
+    ("gray32", [0x52, 0x52, 0x52]),
+    ("grey32", [0x52, 0x52, 0x52]),
+    ("gray33", [0x54, 0x54, 0x54]),
+    ("grey33", [0x54, 0x54, 0x54]),
+    ("gray34", [0x57, 0x57, 0x57]),
+    ("grey34", [0x57, 0x57, 0x57]),
+    ("gray35", [0x59, 0x59, 0x59]),
+    ("grey35", [0x59, 0x59, 0x59]),
+    ("gray36", [0x5c, 0x5c, 0x5c]),
+    ("grey36", [0x5c, 0x5c, 0x5c]),
+    ("gray37", [0x5e, 0x5e, 0x5e]),
+    ("grey37", [0x5e, 0x5e, 0x5e]),
+    ("gray38", [0x61, 0x61, 0x61]),
+    ("grey38", [0x61, 0x61, 0x61]),
+    ("gray39", [0x63, 0x63, 0x63]),
+    ("grey39", [0x63, 0x63, 0x63]),
+    ("gray40", [0x66, 0x66, 0x66]),
+    ("grey40", [0x66, 0x66, 0x66]),
+    ("gray41", [0x69, 0x69, 0x69]),
+    ("grey41", [0x69, 0x69, 0x69]),
+    ("gray42", [0x6b, 0x6b, 0x6b]),
+    ("grey42", [0x6b, 0x6b, 0x6b]),
+    ("gray43", [0x6e, 0x6e, 0x6e]),
+    ("grey43", [0x6e, 0x6e, 0x6e]),
+    ("gray44", [0x70, 0x70, 0x70]),
+    ("grey44", [0x70, 0x70, 0x70]),
+    ("gray45", [0x73, 0x73, 0x73]),
+    ("grey45", [0x73, 0x73, 0x73]),
+    ("gray46", [0x75, 0x75, 0x75]),
+    ("grey46", [0x75, 0x75, 0x75]),
+    ("gray47", [0x78, 0x78, 0x78]),
+    ("grey47", [0x78, 0x78, 0x78]),
+    ("gray48", [0x7a, 0x7a, 0x7a]),
+    ("grey48", [0x7a, 0x7a, 0x7a]),
+    ("gray49", [0x7d, 0x7d, 0x7d]),
+    ("grey49", [0x7d, 0x7d, 0x7d]),
+    ("gray50", [0x7f, 0x7f, 0x7f]),
+    ("grey50", [0x7f, 0x7f, 0x7f]),
+    ("gray51", [0x82, 0x82, 0x82]),
+    ("grey51", [0x82, 0x82, 0x82]),
+    ("gray52", [0x85, 0x85, 0x85]),
+    ("grey52", [0x85, 0x85, 0x85]),
+    ("gray53", [0x87, 0x87, 0x87]),
+    ("grey53", [0x87, 0x87, 0x87]),
+    ("gray54", [0x8a, 0x8a, 0x8a]),
+    ("grey54", [0x8a, 0x8a, 0x8a]),
+    ("gray55", [0x8c, 0x8c, 0x8c]),
+    ("grey55", [0x8c, 0x8c, 0x8c]),
+    ("gray56", [0x8f, 0x8f, 0x8f]),
+    ("grey56", [0x8f, 0x8f, 0x8f]),
+    ("gray57", [0x91, 0x91, 0x91]),
+    ("grey57", [0x91, 0x91, 0x91]),
+    ("gray58", [0x94, 0x94, 0x94]),
+    ("grey58", [0x94, 0x94, 0x94]),
+    ("gray59", [0x96, 0x96, 0x96]),
+    ("grey59", [0x96, 0x96, 0x96]),
+    ("gray60", [0x99, 0x99, 0x99]),
+    ("grey60", [0x99, 0x99, 0x99]),
+    ("gray61", [0x9c, 0x9c, 0x9c]),
+    ("grey61", [0x9c, 0x9c, 0x9c]),
+    ("gray62", [0x9e, 0x9e, 0x9e]),
+    ("grey62", [0x9e, 0x9e, 0x9e]),
+    ("gray63", [0xa1, 0xa1, 0xa1]),
+    ("grey63", [0xa1, 0xa1, 0xa1]),
+    ("gray64", [0xa3, 0xa3, 0xa3]),
+    ("grey64", [0xa3, 0xa3, 0xa3]),
+    ("gray65", [0xa6, 0xa6, 0xa6]),
+    ("grey65", [0xa6, 0xa6, 0xa6]),
+    ("gray66", [0xa8, 0xa8, 0xa8]),
+    ("grey66", [0xa8, 0xa8, 0xa8]),
+    ("gray67", [0xab, 0xab, 0xab]),
+    ("grey67", [0xab, 0xab, 0xab]),
+    ("gray68", [0xad, 0xad, 0xad]),
+    ("grey68", [0xad, 0xad, 0xad]),
+    ("gray69", [0xb0, 0xb0, 0xb0]),
+    ("grey69", [0xb0, 0xb0, 0xb0]),
+    ("gray70", [0xb3, 0xb3, 0xb3]),
+    ("grey70", [0xb3, 0xb3, 0xb3]),
+    ("gray71", [0xb5, 0xb5, 0xb5]),
+    ("grey71", [0xb5, 0xb5, 0xb5]),
+    ("gray72", [0xb8, 0xb8, 0xb8]),
+    ("grey72", [0xb8, 0xb8, 0xb8]),
+    ("gray73", [0xba, 0xba, 0xba]),
+    ("grey73", [0xba, 0xba, 0xba]),
+    ("gray74", [0xbd, 0xbd, 0xbd]),
+    ("grey74", [0xbd, 0xbd, 0xbd]),
+    ("gray75", [0xbf, 0xbf, 0xbf]),
+    ("grey75", [0xbf, 0xbf, 0xbf]),
+    ("gray76", [0xc2, 0xc2, 0xc2]),
+    ("grey76", [0xc2, 0xc2, 0xc2]),
+    ("gray77", [0xc4, 0xc4, 0xc4]),
+    ("grey77", [0xc4, 0xc4, 0xc4]),
+    ("gray78", [0xc7, 0xc7, 0xc7]),
+    ("grey78", [0xc7, 0xc7, 0xc7]),
+    ("gray79", [0xc9, 0xc9, 0xc9]),
+    ("grey79", [0xc9, 0xc9, 0xc9]),
+    ("gray80", [0xcc, 0xcc, 0xcc]),
+    ("grey80", [0xcc, 0xcc, 0xcc]),
+    ("gray81", [0xcf, 0xcf, 0xcf]),
+    ("grey81", [0xcf, 0xcf, 0xcf]),
+    ("gray82", [0xd1, 0xd1, 0xd1]),
+    ("grey82", [0xd1, 0xd1, 0xd1]),
+    ("gray83", [0xd4, 0xd4, 0xd4]),
+    ("grey83", [0xd4, 0xd4, 0xd4]),
+    ("gray84", [0xd6, 0xd6, 0xd6]),
+    ("grey84", [0xd6, 0xd6, 0xd6]),
+    ("gray85", [0xd9, 0xd9, 0xd9]),
+    ("grey85", [0xd9, 0xd9, 0xd9]),
+    ("gray86", [0xdb, 0xdb, 0xdb]),
+    ("grey86", [0xdb, 0xdb, 0xdb]),
+    ("gray87", [0xde, 0xde, 0xde]),
+    ("grey87", [0xde, 0xde, 0xde]),
+    ("gray88", [0xe0, 0xe0, 0xe0]),
+    ("grey88", [0xe0, 0xe0, 0xe0]),
+    ("gray89", [0xe3, 0xe3, 0xe3]),
+    ("grey89", [0xe3, 0xe3, 0xe3]),
+    ("gray90", [0xe5, 0xe5, 0xe5]),
+    ("grey90", [0xe5, 0xe5, 0xe5]),
+    ("gray91", [0xe8, 0xe8, 0xe8]),
+    ("grey91", [0xe8, 0xe8, 0xe8]),
+    ("gray92", [0xeb, 0xeb, 0xeb]),
+    ("grey92", [0xeb, 0xeb, 0xeb]),
+    ("gray93", [0xed, 0xed, 0xed]),
+    ("grey93", [0xed, 0xed, 0xed]),
+    ("gray94", [0xf0, 0xf0, 0xf0]),
+    ("grey94", [0xf0, 0xf0, 0xf0]),
+    ("gray95", [0xf2, 0xf2, 0xf2]),
+    ("grey95", [0xf2, 0xf2, 0xf2]),
+    ("gray96", [0xf5, 0xf5, 0xf5]),
+    ("grey96", [0xf5, 0xf5, 0xf5]),
+    ("gray97", [0xf7, 0xf7, 0xf7]),
+    ("grey97", [0xf7, 0xf7, 0xf7]),
+    ("gray98", [0xfa, 0xfa, 0xfa]),
+    ("grey98", [0xfa, 0xfa, 0xfa]),
+    ("gray99", [0xfc, 0xfc, 0xfc]),
+    ("grey99", [0xfc, 0xfc, 0xfc]),
+    ("gray100", [0xff, 0xff, 0xff]),
+    ("grey100", [0xff, 0xff, 0xff]),
+    ("dark grey", [0xa9, 0xa9, 0xa9]),
+    ("dark gray", [0xa9, 0xa9, 0xa9]),
+    ("dark blue", [0x00, 0x00, 0x8b]),
+    ("dark cyan", [0x00, 0x8b, 0x8b]),
+    ("dark magenta", [0x8b, 0x00, 0x8b]),
+    ("dark red", [0x8b, 0x00, 0x00]),
+    ("light green", [0x90, 0xee, 0x90]),
+];
+
+///Explicit names for the colors that are different between CSS and X11
+///see https://en.wikipedia.org/wiki/X11_color_names#Clashes_between_web_and_X11_colors_in_the_CSS_color_scheme
+pub const CLASH_ALIASES: [(&str, [u8; 3]); 8] = [
+    ("web gray", [0x80, 0x80, 0x80]),
+    ("web green", [0x00, 0x80, 0x00]),
+    ("web maroon", [0x80, 0x00, 0x00]),
+    ("web purple", [0x80, 0x00, 0x80]),
+    ("x11 gray", [0xbe, 0xbe, 0xbe]),
+    ("x11 green", [0x00, 0xff, 0x00]),
+    ("x11 maroon", [0xb0, 0x30, 0x60]),
+    ("x11 purple", [0xa0, 0x20, 0xf0]),
+];
+
+///Names from tpick's original color table that some standards don't have, they're added under
+///every standard's own table so they keep working
+pub const LEGACY_NAMES: [(&str, [u8; 3]); 11] = [
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("light goldenrod", [0xfa, 0xfa, 0xd2]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("navy blue", [0x00, 0x00, 0x80]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("rebecca purple", [0x66, 0x33, 0x99]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("teal", [0x00, 0x80, 0x80]),
+];
@@ -9,6 +9,7 @@ use crate::color_conversions::number2rgb;
 use crate::color_conversions::rgb2ansi256;
 use crate::color_conversions::ColorNameStandard;
 use crate::color_conversions::rgb2number;
+use crate::exit_with_error;
 use crate::hsl2rgb;
 use crate::rgb2hsl;
 use crate::OutputType;
@@ -31,6 +32,23 @@ macro_rules! hashmap {
     }
 }
 
+///The alpha of rgba and hsla colors, either a whole number from 0-255, a fraction from 0-1 such
+///as 0.5 like css uses, or a percentage
+fn parse_alpha(alpha: &str) -> Option<u8> {
+    let alpha = match alpha.strip_suffix("%") {
+        Some(percent) => percent.trim().parse::<f64>().ok()? * 255.0 / 100.0,
+        None => {
+            let value: f64 = alpha.parse().ok()?;
+            if alpha.contains(".") && value <= 1.0 {
+                value * 255.0
+            } else {
+                value
+            }
+        }
+    };
+    (0.0..=255.0).contains(&alpha).then(|| alpha.round() as u8)
+}
+
 #[derive(Clone, Copy)]
 pub struct ColorRepresentation {
     pub r: f64,
@@ -44,80 +62,115 @@ impl ColorRepresentation {
         let (r, g, b) = number2rgb(clr);
         return ColorRepresentation { r: r as f64, g: g as f64, b: b as f64, a: 255 };
     }
+    pub fn from_rgb(r: f64, g: f64, b: f64) -> ColorRepresentation {
+        return ColorRepresentation { r, g, b, a: 255 };
+    }
+    ///Like try_from_color but exits tpick if clr is an unknown color name
     pub fn from_color(clr: &str, clr_name_standard: &ColorNameStandard) -> ColorRepresentation {
+        match Self::try_from_color(clr, clr_name_standard) {
+            Ok(clr) => clr,
+            Err(err) => exit_with_error(&err),
+        }
+    }
+
+    pub fn try_from_color(
+        clr: &str,
+        clr_name_standard: &ColorNameStandard,
+    ) -> Result<ColorRepresentation, String> {
         let r: f64;
         let g: f64;
         let b: f64;
         let mut a = 255;
 
-        let get_next = |split: &mut Split<'_, &str>| split.next().unwrap().trim().parse().unwrap();
-        let get_rgb = |items: &mut Split<'_, &str>| {
-            return (get_next(items), get_next(items), get_next(items));
+        let invalid = || format!("Invalid color: {}", clr);
+        let get_next = |split: &mut Split<'_, &str>| -> Result<f64, String> {
+            split
+                .next()
+                .and_then(|item| item.trim().parse().ok())
+                .ok_or_else(invalid)
+        };
+        let get_rgb = |items: &mut Split<'_, &str>| -> Result<(f64, f64, f64), String> {
+            Ok((get_next(items)?, get_next(items)?, get_next(items)?))
+        };
+        let get_alpha = |items: &mut Split<'_, &str>| -> Result<u8, String> {
+            items
+                .next()
+                .and_then(|item| parse_alpha(item.trim()))
+                .ok_or_else(invalid)
+        };
+        //what is between the prefix and the closing parenthesis
+        let inner = |prefix: usize| clr.get(prefix..clr.len() - 1).ok_or_else(invalid);
+        let hex = |range: std::ops::Range<usize>| {
+            i64::from_str_radix(&clr[range], 16).map_err(|_| invalid())
         };
 
         if clr.starts_with("\\x1b") {
             //\x1b[38;2;
-            let mut items = clr[10..clr.len() - 1].split(";");
-            (r, g, b) = get_rgb(&mut items);
+            let mut items = inner(10)?.split(";");
+            (r, g, b) = get_rgb(&mut items)?;
         } else if clr.contains(";") {
             let mut items = clr.split(";");
-            (r, g, b) = get_rgb(&mut items);
+            (r, g, b) = get_rgb(&mut items)?;
         } else if clr.starts_with("rgba") {
-            let mut items = clr[5..clr.len() - 1].split(",");
-            (r, g, b) = get_rgb(&mut items);
-            a = items.next().unwrap().trim().parse().unwrap();
+            let mut items = inner(5)?.split(",");
+            (r, g, b) = get_rgb(&mut items)?;
+            a = get_alpha(&mut items)?;
         } else if clr.starts_with("rgb") {
-            let mut items = clr[4..clr.len() - 1].split(",");
-            (r, g, b) = get_rgb(&mut items);
+            let mut items = inner(4)?.split(",");
+            (r, g, b) = get_rgb(&mut items)?;
         } else if clr.starts_with("hsla") {
-            let mut items = clr[5..clr.len() - 1].split(",");
-            let h: f64 = get_next(&mut items);
-            let s: f64 = get_next(&mut items);
-            let l: f64 = get_next(&mut items);
-            a = items.next().unwrap().trim().parse().unwrap();
+            let mut items = inner(5)?.split(",");
+            let h: f64 = get_next(&mut items)?;
+            let s: f64 = get_next(&mut items)?;
+            let l: f64 = get_next(&mut items)?;
+            a = get_alpha(&mut items)?;
             (r, g, b) = hsl2rgb(h, s, l);
         } else if clr.starts_with("hsl") {
-            let mut items = clr[4..clr.len() - 1].split(",");
-            let h: f64 = get_next(&mut items);
-            let s: f64 = get_next(&mut items);
-            let l: f64 = get_next(&mut items);
+            let mut items = inner(4)?.split(",");
+            let h: f64 = get_next(&mut items)?;
+            let s: f64 = get_next(&mut items)?;
+            let l: f64 = get_next(&mut items)?;
             (r, g, b) = hsl2rgb(h, s, l);
         }        //#RGB or #RGBA or #RRGGBB or #RRGGBBAA
         else if clr.starts_with("#")
+            && clr.is_ascii()
             && (clr.len() == 4 || clr.len() == 5 || clr.len() == 7 || clr.len() == 9)
         {
             match clr.len() {
                 4 => {
-                    r = (i64::from_str_radix(&clr[1..2], 16).unwrap() as f64).powi(2);
-                    g = (i64::from_str_radix(&clr[2..3], 16).unwrap() as f64).powi(2);
-                    b = (i64::from_str_radix(&clr[3..4], 16).unwrap() as f64).powi(2);
+                    r = (hex(1..2)? as f64).powi(2);
+                    g = (hex(2..3)? as f64).powi(2);
+                    b = (hex(3..4)? as f64).powi(2);
                 }
                 5 => {
-                    r = (i64::from_str_radix(&clr[1..2], 16).unwrap() as f64).powi(2);
-                    g = (i64::from_str_radix(&clr[2..3], 16).unwrap() as f64).powi(2);
-                    b = (i64::from_str_radix(&clr[3..4], 16).unwrap() as f64).powi(2);
-                    a = (i64::from_str_radix(&clr[4..5], 16).unwrap()).pow(2) as u8;
+                    r = (hex(1..2)? as f64).powi(2);
+                    g = (hex(2..3)? as f64).powi(2);
+                    b = (hex(3..4)? as f64).powi(2);
+                    a = hex(4..5)?.pow(2) as u8;
                 }
                 7 => {
-                    r = i64::from_str_radix(&clr[1..3], 16).unwrap() as f64;
-                    g = i64::from_str_radix(&clr[3..5], 16).unwrap() as f64;
-                    b = i64::from_str_radix(&clr[5..7], 16).unwrap() as f64;
+                    r = hex(1..3)? as f64;
+                    g = hex(3..5)? as f64;
+                    b = hex(5..7)? as f64;
                 }
                 9 => {
-                    r = i64::from_str_radix(&clr[1..3], 16).unwrap() as f64;
-                    g = i64::from_str_radix(&clr[3..5], 16).unwrap() as f64;
-                    b = i64::from_str_radix(&clr[5..7], 16).unwrap() as f64;
-                    a = i64::from_str_radix(&clr[7..9], 16).unwrap() as u8;
+                    r = hex(1..3)? as f64;
+                    g = hex(3..5)? as f64;
+                    b = hex(5..7)? as f64;
+                    a = hex(7..9)? as u8;
                 }
                 _ => {
                     (r, g, b) = (0.0, 0.0, 0.0);
                 }
             }
         } else {
-            let (r8, g8, b8) = hex62rgb(&name_to_hex(clr, clr_name_standard)[1..]);
+            let Some(hex) = name_to_hex(clr, clr_name_standard) else {
+                return Err(format!("Unknown color: {}", clr));
+            };
+            let (r8, g8, b8) = hex62rgb(&hex[1..]);
             (r, g, b) = (r8 as f64, g8 as f64, b8 as f64);
         }
-        ColorRepresentation { r, g, b, a }
+        Ok(ColorRepresentation { r, g, b, a })
     }
 
     pub fn make_square(&self) -> String {
@@ -309,6 +362,17 @@ impl ColorRepresentation {
     pub fn toansi(&self, _enable_alpha: bool) -> String {
         return format!("{};{};{}", self.r as u8, self.g as u8, self.b as u8);
     }
+
+    ///Every channel of the color, used for --json output
+    pub fn to_json(&self) -> serde_json::Value {
+        let (h, s, l) = self.hsl();
+        serde_json::json!({
+            "hex": format!("#{}", self.tohex(false)),
+            "rgb": { "r": self.r, "g": self.g, "b": self.b },
+            "hsl": { "h": h, "s": s, "l": l },
+            "alpha": self.a,
+        })
+    }
 }
 
 impl Display for ColorRepresentation {
@@ -0,0 +1,181 @@
+use crate::color_conversions::{oklab2rgb, rgb2oklab};
+use crate::image::Image;
+use crate::random::Rng;
+
+///At most this many pixels are looked at, big images are sampled evenly
+const MAX_SAMPLES: usize = 50000;
+const KMEANS_ITERATIONS: usize = 30;
+
+#[derive(clap::ValueEnum, Debug, Clone, PartialEq, Copy)]
+pub enum ExtractMethod {
+    #[value(name = "kmeans", alias = "k-means")]
+    KMeans,
+    #[value(alias = "median-cut")]
+    MeanCut,
+}
+
+///A dominant color of an image and how much of the image it covers from 0-1
+pub struct Swatch {
+    pub rgb: [f64; 3],
+    pub proportion: f64,
+}
+
+///The OKLab colors of the pixels, mostly transparent pixels are left out
+fn samples(image: &Image) -> Vec<[f64; 3]> {
+    let step = (image.pixels.len() / MAX_SAMPLES).max(1);
+    image
+        .pixels
+        .iter()
+        .step_by(step)
+        .filter(|px| px[3] >= 128)
+        .map(|px| {
+            let (l, a, b) = rgb2oklab(px[0] as f64, px[1] as f64, px[2] as f64);
+            [l, a, b]
+        })
+        .collect()
+}
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+fn mean(points: &[[f64; 3]]) -> [f64; 3] {
+    let mut sum = [0.0; 3];
+    for point in points {
+        for i in 0..3 {
+            sum[i] += point[i];
+        }
+    }
+    sum.map(|total| total / points.len() as f64)
+}
+
+///The (widest axis, its range) of a box of points
+fn widest_axis(points: &[[f64; 3]]) -> (usize, f64) {
+    (0..3)
+        .map(|axis| {
+            let (min, max) = points.iter().fold((f64::MAX, f64::MIN), |(min, max), p| {
+                (min.min(p[axis]), max.max(p[axis]))
+            });
+            (axis, max - min)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+}
+
+///Splits the box with the most colors times spread at the mean of its widest axis until there
+///are count boxes, cutting at the mean instead of the median keeps a box from straddling two
+///colors when one of them covers most of the image
+fn mean_cut(points: &mut [[f64; 3]], count: usize) -> Vec<([f64; 3], usize)> {
+    let mut boxes = vec![(0, points.len())];
+    while boxes.len() < count {
+        let Some((idx, axis)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, (start, end))| end - start > 1)
+            .map(|(idx, (start, end))| {
+                let (axis, range) = widest_axis(&points[*start..*end]);
+                (idx, axis, range * (end - start) as f64)
+            })
+            .filter(|(_, _, score)| *score > 0.0)
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(idx, axis, _)| (idx, axis))
+        else {
+            break;
+        };
+        let (start, end) = boxes[idx];
+        points[start..end].sort_by(|a, b| a[axis].total_cmp(&b[axis]));
+        //the axis has a spread, so both sides of the mean have points
+        let cut = mean(&points[start..end])[axis];
+        let middle = start + points[start..end].partition_point(|p| p[axis] < cut);
+        boxes[idx] = (start, middle);
+        boxes.push((middle, end));
+    }
+    boxes
+        .into_iter()
+        .map(|(start, end)| (mean(&points[start..end]), end - start))
+        .collect()
+}
+
+///k-means++ to pick the starting centers, then Lloyd's algorithm
+fn kmeans(points: &[[f64; 3]], count: usize, rng: &mut Rng) -> Vec<([f64; 3], usize)> {
+    let mut centers = vec![points[(rng.next_u64() % points.len() as u64) as usize]];
+    let mut nearest: Vec<f64> = points.iter().map(|p| distance(p, &centers[0])).collect();
+    while centers.len() < count {
+        let total: f64 = nearest.iter().sum();
+        if total == 0.0 {
+            break;
+        }
+        //far away points are more likely to become centers
+        let mut target = rng.next_f64() * total;
+        let idx = nearest
+            .iter()
+            .position(|d| {
+                target -= d;
+                target < 0.0
+            })
+            .unwrap_or(points.len() - 1);
+        centers.push(points[idx]);
+        for (p, d) in points.iter().zip(nearest.iter_mut()) {
+            *d = d.min(distance(p, &points[idx]));
+        }
+    }
+    let mut assignments = vec![0; points.len()];
+    let mut sizes = vec![0; centers.len()];
+    for iteration in 0..KMEANS_ITERATIONS {
+        let mut changed = false;
+        for (p, assignment) in points.iter().zip(assignments.iter_mut()) {
+            let closest = (0..centers.len())
+                .min_by(|a, b| distance(p, &centers[*a]).total_cmp(&distance(p, &centers[*b])))
+                .unwrap();
+            if closest != *assignment || iteration == 0 {
+                *assignment = closest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        let mut sums = vec![[0.0; 3]; centers.len()];
+        sizes = vec![0; centers.len()];
+        for (p, assignment) in points.iter().zip(&assignments) {
+            for i in 0..3 {
+                sums[*assignment][i] += p[i];
+            }
+            sizes[*assignment] += 1;
+        }
+        for (center, (sum, size)) in centers.iter_mut().zip(sums.iter().zip(&sizes)) {
+            if *size > 0 {
+                *center = sum.map(|total| total / *size as f64);
+            }
+        }
+    }
+    centers.into_iter().zip(sizes).collect()
+}
+
+///The count most common colors of the image, sorted by how much of the image they cover
+pub fn extract(image: &Image, count: usize, method: &ExtractMethod) -> Result<Vec<Swatch>, String> {
+    if count == 0 {
+        return Err("The count has to be at least 1".to_owned());
+    }
+    let mut points = samples(image);
+    if points.is_empty() {
+        return Err("The image has no opaque pixels".to_owned());
+    }
+    let clusters = match method {
+        ExtractMethod::KMeans => kmeans(&points, count, &mut Rng::new(0)),
+        ExtractMethod::MeanCut => mean_cut(&mut points, count),
+    };
+    let mut swatches: Vec<Swatch> = clusters
+        .into_iter()
+        .filter(|(_, size)| *size > 0)
+        .map(|([l, a, b], size)| {
+            let (r, g, b) = oklab2rgb(l, a, b);
+            Swatch {
+                rgb: [r, g, b],
+                proportion: size as f64 / points.len() as f64,
+            }
+        })
+        .collect();
+    swatches.sort_by(|a, b| b.proportion.total_cmp(&a.proportion));
+    Ok(swatches)
+}
@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use crate::{jpeg, png};
+
+///A decoded image, every pixel is rgba
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+///Images with more pixels than this are refused instead of trying to allocate them
+const MAX_PIXELS: usize = 1 << 26;
+
+///The number of pixels of a width by height image, or an error if there are none or too many
+pub fn pixel_count(width: usize, height: usize) -> Result<usize, String> {
+    match width.checked_mul(height) {
+        Some(count) if count > 0 && count <= MAX_PIXELS => Ok(count),
+        _ => Err(format!("Unsupported image size: {}x{}", width, height)),
+    }
+}
+
+///Decodes a PNG, JPEG or PPM/PGM file, the format is detected from the contents
+pub fn load(path: &Path) -> Result<Image, String> {
+    let data =
+        std::fs::read(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+    let image = if data.starts_with(png::SIGNATURE) {
+        png::decode(&data)
+    } else if data.starts_with(&[0xff, 0xd8]) {
+        jpeg::decode(&data)
+    } else if data.len() > 2 && data[0] == b'P' && matches!(data[1], b'2' | b'3' | b'5' | b'6') {
+        decode_pnm(&data)
+    } else {
+        Err("Unknown image format, expected PNG, JPEG or PPM".to_owned())
+    };
+    image.map_err(|err| format!("Could not decode {}: {}", path.display(), err))
+}
+
+///Reads the whitespace separated numbers of a PNM header or ascii body, skipping comments
+struct PnmTokens<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl PnmTokens<'_> {
+    fn next_number(&mut self) -> Result<u32, String> {
+        loop {
+            match self.data.get(self.pos) {
+                Some(b'#') => {
+                    while self.data.get(self.pos).is_some_and(|c| *c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(_) => break,
+                None => return Err("Unexpected end of file".to_owned()),
+            }
+        }
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| "Invalid number".to_owned())
+    }
+}
+
+///PGM and PPM, in both their ascii (P2, P3) and binary (P5, P6) forms
+fn decode_pnm(data: &[u8]) -> Result<Image, String> {
+    let kind = data[1];
+    let mut tokens = PnmTokens { data, pos: 2 };
+    let width = tokens.next_number()? as usize;
+    let height = tokens.next_number()? as usize;
+    let max = tokens.next_number()?;
+    if max == 0 || max > 65535 {
+        return Err(format!("Invalid maximum value: {}", max));
+    }
+    let channels = if kind == b'3' || kind == b'6' { 3 } else { 1 };
+    let count = pixel_count(width, height)? * channels;
+    let truncated = || "Unexpected end of file".to_owned();
+    let mut samples;
+    if kind == b'2' || kind == b'3' {
+        //every sample takes at least one digit
+        if data.len() - tokens.pos < count {
+            return Err(truncated());
+        }
+        samples = Vec::with_capacity(count);
+        for _ in 0..count {
+            samples.push(tokens.next_number()?);
+        }
+    } else {
+        //a single whitespace character separates the header from the binary data
+        let body = data.get(tokens.pos + 1..).ok_or_else(truncated)?;
+        let size = if max > 255 { 2 } else { 1 };
+        if body.len() < count * size {
+            return Err(truncated());
+        }
+        samples = Vec::with_capacity(count);
+        samples.extend(body.chunks(size).take(count).map(|sample| match sample {
+            [hi, lo] => (*hi as u32) << 8 | *lo as u32,
+            _ => sample[0] as u32,
+        }));
+    }
+    let scale = |sample: u32| (sample.min(max) * 255 / max) as u8;
+    let pixels = samples
+        .chunks(channels)
+        .map(|px| match px {
+            [r, g, b] => [scale(*r), scale(*g), scale(*b), 255],
+            _ => [scale(px[0]), scale(px[0]), scale(px[0]), 255],
+        })
+        .collect();
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
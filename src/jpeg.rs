@@ -0,0 +1,470 @@
+use crate::image::{pixel_count, Image};
+
+///Where each coefficient, in the order they are stored, goes in the 8x8 block
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+#[derive(Clone, Default)]
+struct Huffman {
+    ///The largest code of each length, or -1 if there are none
+    max_code: [i32; 17],
+    ///The index in values of the first code of each length, minus that code
+    offset: [i32; 17],
+    values: Vec<u8>,
+}
+
+impl Huffman {
+    fn new(counts: &[u8], values: &[u8]) -> Huffman {
+        let mut huffman = Huffman {
+            max_code: [-1; 17],
+            offset: [0; 17],
+            values: values.to_vec(),
+        };
+        let (mut code, mut index) = (0i32, 0i32);
+        for len in 1..=16 {
+            let count = counts[len - 1] as i32;
+            if count > 0 {
+                huffman.offset[len] = index - code;
+                code += count;
+                index += count;
+                huffman.max_code[len] = code - 1;
+            }
+            code <<= 1;
+        }
+        huffman
+    }
+}
+
+struct Component {
+    id: u8,
+    h: usize,
+    v: usize,
+    quant: usize,
+    dc_table: usize,
+    ac_table: usize,
+    dc_pred: i32,
+    ///Width of the decoded plane, a whole number of blocks
+    stride: usize,
+    plane: Vec<u8>,
+}
+
+///Reads the entropy coded data, most significant bit first, skipping the stuffed zero bytes
+///Once a marker is reached it returns zeros
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buf: u32,
+    count: u32,
+}
+
+impl BitReader<'_> {
+    fn fill(&mut self) {
+        while self.count <= 24 {
+            let mut byte = 0;
+            if let Some(b) = self.data.get(self.pos) {
+                if *b == 0xff {
+                    //anything but a stuffed zero is a marker, leave it for the caller
+                    if self.data.get(self.pos + 1) == Some(&0) {
+                        byte = 0xff;
+                        self.pos += 2;
+                    }
+                } else {
+                    byte = *b;
+                    self.pos += 1;
+                }
+            }
+            self.buf |= (byte as u32) << (24 - self.count);
+            self.count += 8;
+        }
+    }
+
+    fn bits(&mut self, n: u32) -> u32 {
+        if n == 0 {
+            return 0;
+        }
+        self.fill();
+        let value = self.buf >> (32 - n);
+        self.buf <<= n;
+        self.count -= n;
+        value
+    }
+
+    fn decode(&mut self, table: &Huffman) -> Result<u8, String> {
+        let mut code = 0i32;
+        for len in 1..=16 {
+            code = (code << 1) | self.bits(1) as i32;
+            if code <= table.max_code[len] {
+                return table
+                    .values
+                    .get((table.offset[len] + code) as usize)
+                    .copied()
+                    .ok_or_else(|| "Invalid huffman code".to_owned());
+            }
+        }
+        Err("Invalid huffman code".to_owned())
+    }
+
+    ///Reads an s bit value where the values with a leading 0 are negative
+    fn receive_extend(&mut self, s: u32) -> i32 {
+        let value = self.bits(s) as i32;
+        if s > 0 && value < 1 << (s - 1) {
+            value - (1 << s) + 1
+        } else {
+            value
+        }
+    }
+
+    ///Goes past the restart marker and forgets the buffered bits
+    fn restart(&mut self) {
+        self.buf = 0;
+        self.count = 0;
+        while self.pos + 1 < self.data.len()
+            && !(self.data[self.pos] == 0xff && (0xd0..=0xd7).contains(&self.data[self.pos + 1]))
+        {
+            self.pos += 1;
+        }
+        self.pos += 2;
+    }
+}
+
+///C(u) * cos((2x + 1)uπ/16) at x * 8 + u
+fn cos_table() -> [f32; 64] {
+    std::array::from_fn(|i| {
+        let (x, u) = (i / 8, i % 8);
+        let c = if u == 0 {
+            std::f32::consts::FRAC_1_SQRT_2
+        } else {
+            1.0
+        };
+        c * (((2 * x + 1) * u) as f32 * std::f32::consts::PI / 16.0).cos()
+    })
+}
+
+///A direct 2d inverse DCT, slow but fine for the sizes involved
+fn idct(coefficients: &[i32; 64], cos_table: &[f32; 64], out: &mut [u8], stride: usize) {
+    let mut rows = [0f32; 64];
+    for y in 0..8 {
+        for u in 0..8 {
+            rows[y * 8 + u] = (0..8)
+                .map(|v| cos_table[y * 8 + v] * coefficients[v * 8 + u] as f32)
+                .sum();
+        }
+    }
+    for y in 0..8 {
+        for x in 0..8 {
+            let value: f32 = (0..8)
+                .map(|u| cos_table[x * 8 + u] * rows[y * 8 + u])
+                .sum::<f32>()
+                / 4.0;
+            out[y * stride + x] = (value + 128.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Result<usize, String> {
+    match data.get(pos..pos + 2) {
+        Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]]) as usize),
+        None => Err("Unexpected end of file".to_owned()),
+    }
+}
+
+///Decodes a baseline (or extended sequential) huffman coded JPEG with 1 or 3 components
+pub fn decode(data: &[u8]) -> Result<Image, String> {
+    let mut quant = [[0i32; 64]; 4];
+    let mut dc_tables = vec![Huffman::default(); 4];
+    let mut ac_tables = vec![Huffman::default(); 4];
+    let mut components: Vec<Component> = vec![];
+    let (mut width, mut height) = (0, 0);
+    let mut restart_interval = 0;
+    let mut pos = 2;
+    loop {
+        //markers can be padded with any number of 0xff
+        while data.get(pos) == Some(&0xff) && data.get(pos + 1) == Some(&0xff) {
+            pos += 1;
+        }
+        if data.get(pos) != Some(&0xff) {
+            return Err("Expected a marker".to_owned());
+        }
+        let marker = *data.get(pos + 1).ok_or("Unexpected end of file")?;
+        pos += 2;
+        if marker == 0xd9 {
+            break;
+        }
+        //markers without a length
+        if marker == 0x01 || (0xd0..=0xd8).contains(&marker) {
+            continue;
+        }
+        let len = read_u16(data, pos)?;
+        let segment = data
+            .get(pos + 2..pos + len)
+            .ok_or_else(|| "Unexpected end of file".to_owned())?;
+        match marker {
+            //DQT
+            0xdb => {
+                let mut i = 0;
+                while i < segment.len() {
+                    let (precision, id) = (segment[i] >> 4, (segment[i] & 3) as usize);
+                    i += 1;
+                    for (k, q) in quant[id].iter_mut().enumerate() {
+                        *q = if precision == 0 {
+                            *segment.get(i + k).ok_or("Truncated quantization table")? as i32
+                        } else {
+                            read_u16(segment, i + k * 2)? as i32
+                        };
+                    }
+                    i += 64 * (precision as usize + 1);
+                }
+            }
+            //SOF0 baseline and SOF1 extended sequential
+            0xc0 | 0xc1 => {
+                if segment.len() < 6 || segment[0] != 8 {
+                    return Err("Only 8 bit JPEGs are supported".to_owned());
+                }
+                if !components.is_empty() {
+                    return Err("More than one frame header".to_owned());
+                }
+                height = read_u16(segment, 1)?;
+                width = read_u16(segment, 3)?;
+                pixel_count(width, height)?;
+                for c in segment[6..].chunks(3).take(segment[5] as usize) {
+                    if c.len() < 3 {
+                        return Err("Truncated frame header".to_owned());
+                    }
+                    components.push(Component {
+                        id: c[0],
+                        h: (c[1] >> 4).max(1) as usize,
+                        v: (c[1] & 15).max(1) as usize,
+                        quant: (c[2] & 3) as usize,
+                        dc_table: 0,
+                        ac_table: 0,
+                        dc_pred: 0,
+                        stride: 0,
+                        plane: vec![],
+                    });
+                }
+                if components.len() != 1 && components.len() != 3 {
+                    return Err(format!(
+                        "Unsupported number of components: {}",
+                        components.len()
+                    ));
+                }
+                let h_max = components.iter().map(|c| c.h).max().unwrap();
+                let v_max = components.iter().map(|c| c.v).max().unwrap();
+                let mcus_x = width.div_ceil(8 * h_max);
+                let mcus_y = height.div_ceil(8 * v_max);
+                for c in &mut components {
+                    c.stride = mcus_x * c.h * 8;
+                    let size = c
+                        .stride
+                        .checked_mul(mcus_y * c.v * 8)
+                        .ok_or_else(|| format!("Unsupported image size: {}x{}", width, height))?;
+                    c.plane = vec![0; size];
+                }
+            }
+            0xc2 | 0xc3 | 0xc5..=0xc7 | 0xc9..=0xcb | 0xcd..=0xcf => {
+                return Err(
+                    "Only baseline JPEGs are supported, progressive or arithmetic coded ones are not"
+                        .to_owned(),
+                );
+            }
+            //DHT
+            0xc4 => {
+                let mut i = 0;
+                while i + 17 <= segment.len() {
+                    let (class, id) = (segment[i] >> 4, (segment[i] & 3) as usize);
+                    let counts = &segment[i + 1..i + 17];
+                    let total: usize = counts.iter().map(|c| *c as usize).sum();
+                    let values = segment
+                        .get(i + 17..i + 17 + total)
+                        .ok_or("Truncated huffman table")?;
+                    let table = Huffman::new(counts, values);
+                    if class == 0 {
+                        dc_tables[id] = table;
+                    } else {
+                        ac_tables[id] = table;
+                    }
+                    i += 17 + total;
+                }
+            }
+            //DRI
+            0xdd => restart_interval = read_u16(segment, 0)?,
+            //SOS
+            0xda => {
+                if components.is_empty() {
+                    return Err("Scan before the frame header".to_owned());
+                }
+                let count = *segment.first().ok_or("Truncated scan header")? as usize;
+                if segment.len() < 1 + 2 * count {
+                    return Err("Truncated scan header".to_owned());
+                }
+                let mut scan = vec![];
+                for c in segment[1..].chunks_exact(2).take(count) {
+                    let idx = components
+                        .iter()
+                        .position(|comp| comp.id == c[0])
+                        .ok_or("Scan of an unknown component")?;
+                    components[idx].dc_table = (c[1] >> 4) as usize & 3;
+                    components[idx].ac_table = (c[1] & 15) as usize & 3;
+                    scan.push(idx);
+                }
+                pos += len;
+                pos = decode_scan(
+                    data,
+                    pos,
+                    &mut components,
+                    &scan,
+                    &quant,
+                    &dc_tables,
+                    &ac_tables,
+                    restart_interval,
+                    (width, height),
+                )?;
+                continue;
+            }
+            //APPn, COM and the rest have nothing we need
+            _ => {}
+        }
+        pos += len;
+    }
+    if components.is_empty() {
+        return Err("Missing frame header".to_owned());
+    }
+    Ok(Image {
+        width,
+        height,
+        pixels: to_rgba(&components, width, height),
+    })
+}
+
+///Decodes the entropy coded data of a scan and returns where it ends
+#[allow(clippy::too_many_arguments)]
+fn decode_scan(
+    data: &[u8],
+    pos: usize,
+    components: &mut [Component],
+    scan: &[usize],
+    quant: &[[i32; 64]; 4],
+    dc_tables: &[Huffman],
+    ac_tables: &[Huffman],
+    restart_interval: usize,
+    (width, height): (usize, usize),
+) -> Result<usize, String> {
+    let h_max = components.iter().map(|c| c.h).max().unwrap();
+    let v_max = components.iter().map(|c| c.v).max().unwrap();
+    //a scan with one component goes through its blocks one by one, interleaved scans go
+    //through MCUs made of h*v blocks of each component
+    let (mcus_x, mcus_y) = if scan.len() == 1 {
+        let c = &components[scan[0]];
+        (
+            (width * c.h).div_ceil(h_max).div_ceil(8),
+            (height * c.v).div_ceil(v_max).div_ceil(8),
+        )
+    } else {
+        (width.div_ceil(8 * h_max), height.div_ceil(8 * v_max))
+    };
+    let mut reader = BitReader {
+        data,
+        pos,
+        buf: 0,
+        count: 0,
+    };
+    for c in components.iter_mut() {
+        c.dc_pred = 0;
+    }
+    let mut block = [0i32; 64];
+    let cos_table = cos_table();
+    for mcu in 0..mcus_x * mcus_y {
+        if restart_interval > 0 && mcu > 0 && mcu % restart_interval == 0 {
+            reader.restart();
+            for c in components.iter_mut() {
+                c.dc_pred = 0;
+            }
+        }
+        let (mx, my) = (mcu % mcus_x, mcu / mcus_x);
+        for idx in scan {
+            let c = &mut components[*idx];
+            let (blocks_x, blocks_y) = if scan.len() == 1 { (1, 1) } else { (c.h, c.v) };
+            for by in 0..blocks_y {
+                for bx in 0..blocks_x {
+                    block.fill(0);
+                    let q = &quant[c.quant];
+                    let s = reader.decode(&dc_tables[c.dc_table])? as u32;
+                    if s > 11 {
+                        return Err(format!("Invalid DC difference size: {}", s));
+                    }
+                    //corrupt files can push the values past what a real image would have
+                    c.dc_pred = c.dc_pred.wrapping_add(reader.receive_extend(s));
+                    block[0] = c.dc_pred.wrapping_mul(q[0]);
+                    let mut k = 1;
+                    while k < 64 {
+                        let rs = reader.decode(&ac_tables[c.ac_table])?;
+                        let (run, size) = ((rs >> 4) as usize, (rs & 15) as u32);
+                        if size == 0 {
+                            if run != 15 {
+                                break;
+                            }
+                            k += 16;
+                            continue;
+                        }
+                        if size > 10 {
+                            return Err(format!("Invalid AC coefficient size: {}", size));
+                        }
+                        k += run;
+                        if k > 63 {
+                            break;
+                        }
+                        block[ZIGZAG[k]] = reader.receive_extend(size).wrapping_mul(q[k]);
+                        k += 1;
+                    }
+                    let x = (mx * blocks_x + bx) * 8;
+                    let y = (my * blocks_y + by) * 8;
+                    if x + 8 <= c.stride && (y + 8) * c.stride <= c.plane.len() {
+                        let stride = c.stride;
+                        idct(&block, &cos_table, &mut c.plane[y * stride + x..], stride);
+                    }
+                }
+            }
+        }
+    }
+    //the scan ends at the next marker that isn't a restart marker
+    let mut end = reader.pos;
+    while end + 1 < data.len()
+        && !(data[end] == 0xff && data[end + 1] != 0 && !(0xd0..=0xd7).contains(&data[end + 1]))
+    {
+        end += 1;
+    }
+    Ok(end)
+}
+
+fn to_rgba(components: &[Component], width: usize, height: usize) -> Vec<[u8; 4]> {
+    let h_max = components.iter().map(|c| c.h).max().unwrap();
+    let v_max = components.iter().map(|c| c.v).max().unwrap();
+    //subsampled components are scaled up by repeating their samples
+    let sample = |c: &Component, x: usize, y: usize| {
+        c.plane[(y * c.v / v_max) * c.stride + x * c.h / h_max] as f32
+    };
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            if components.len() == 1 {
+                let v = sample(&components[0], x, y) as u8;
+                pixels.push([v, v, v, 255]);
+                continue;
+            }
+            let luma = sample(&components[0], x, y);
+            let cb = sample(&components[1], x, y) - 128.0;
+            let cr = sample(&components[2], x, y) - 128.0;
+            let to_u8 = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+            pixels.push([
+                to_u8(luma + 1.402 * cr),
+                to_u8(luma - 0.344136 * cb - 0.714136 * cr),
+                to_u8(luma + 1.772 * cb),
+                255,
+            ]);
+        }
+    }
+    pixels
+}
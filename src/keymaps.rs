@@ -1,13 +1,17 @@
 use std::collections::HashMap;
 
+use crate::color_conversions::{fix_contrast, Deficiency, Harmony, LightnessSpace, MixSpace};
 use crate::ConfigOutput;
 use crate::cls;
 use crate::hashmap;
+use crate::get_input;
+use crate::harmony_colors;
+use crate::randomize;
+use crate::registers::Registers;
 use crate::ui;
 use crate::ColorRepresentation;
 use crate::Config;
 use crate::ProgramState;
-use crate::{paste_to_clipboard, read_clipboard};
 use crate::{OutputType, SelectionType};
 
 pub enum Action {
@@ -33,7 +37,16 @@ fn read_keymap_from_config(config: &Config) -> HashMap<String, String> {
         "copy".to_owned() => "y".to_owned(),
         "copy-raw".to_owned() => "Y".to_owned(),
         "paste".to_owned() => "p".to_owned(),
+        "select-register".to_owned() => "\"".to_owned(),
         "toggle-alpha".to_owned() => "a".to_owned(),
+        "cycle-contrast-algorithm".to_owned() => "c".to_owned(),
+        "fix-contrast".to_owned() => "F".to_owned(),
+        "cycle-simulation".to_owned() => "v".to_owned(),
+        "cycle-harmony".to_owned() => "t".to_owned(),
+        "cycle-harmony-space".to_owned() => "T".to_owned(),
+        "pick-harmony".to_owned() => "P".to_owned(),
+        "cycle-mix-space".to_owned() => "m".to_owned(),
+        "randomize".to_owned() => "r".to_owned(),
         "00%".to_owned() => "0".to_owned(),
         "10%".to_owned() => "1".to_owned(),
         "20%".to_owned() => "2".to_owned(),
@@ -83,11 +96,13 @@ pub fn init_keymaps(
         Some(Action::Break)
     });
     insert("quit-and-copy".to_string(), |program_state, _key| {
-        paste_to_clipboard(
-            &program_state
-                .output_type
-                .render_output(&program_state.curr_color, program_state.enable_alpha),
-        );
+        let output = program_state
+            .output_type
+            .render_output(&program_state.curr_color, program_state.enable_alpha);
+        if let Err(err) = program_state.copy(&output) {
+            program_state.status = Some(err);
+            return None;
+        }
         Some(Action::Break)
     });
     insert("set-max-value".to_string(), |program_state, _key| {
@@ -199,7 +214,6 @@ pub fn init_keymaps(
     });
 
     insert("set-value".to_string(), |program_state, _key| {
-        let mut reader = std::io::stdin();
         let n = ui::input(
             &format!(
                 "Set value {}: ",
@@ -207,7 +221,6 @@ pub fn init_keymaps(
                     .selection_type
                     .label_from_selected_item(program_state.selected_item)
             ),
-            &mut reader,
             30,
             1,
         );
@@ -216,7 +229,7 @@ pub fn init_keymaps(
             let sel_type = program_state.selection_type;
             sel_type.modify_color_based_on_selected_item(program_state, n);
         } else {
-            print!("\x1b[s\x1b[30;1H\x1b[31m{}\x1b[0m\x1b[u", "Invalid number");
+            program_state.status = Some("Invalid number".to_owned());
         };
         None
     });
@@ -228,15 +241,13 @@ pub fn init_keymaps(
     });
 
     insert("select-output".to_string(), |program_state, _key| {
-        let mut reader = std::io::stdin();
         let how_to_select = ui::selection_menu(
             vec!["select output", "custom format", "all outputs", "select output cycle"],
-            &mut reader,
             30,
             1,
         );
         if how_to_select == "custom format" {
-            let fmt = ui::input("Format: ", &mut reader, 30, 1);
+            let fmt = ui::input("Format: ", 30, 1);
             program_state.output_idx = 0; //restart the cycle
             program_state.output_type = OutputType::CUSTOM(fmt);
         } else if how_to_select == "all outputs" {
@@ -248,7 +259,7 @@ pub fn init_keymaps(
                     }
             )])[0];
             let items: Vec<&String> = outputs.keys().collect();
-            let cycle = ui::selection_menu(items, &mut reader, 30, 1);
+            let cycle = ui::selection_menu(items, 30, 1);
             program_state.output_order = OutputType::get_order_by_name(&program_state.config, &cycle).unwrap();
             program_state.output_idx = 0;
             program_state.next_output();
@@ -260,7 +271,6 @@ pub fn init_keymaps(
                     OutputType::HEX,
                     OutputType::ANSI,
                 ],
-                &mut reader,
                 20,
                 1,
             );
@@ -270,34 +280,64 @@ pub fn init_keymaps(
     });
 
     insert("input-new-color".to_string(), |program_state, _key| {
-        let mut reader = std::io::stdin();
-        let clr = ui::input("New color: ", &mut reader, 30, 1);
-        program_state.curr_color = ColorRepresentation::from_color(&clr, &program_state.clr_std);
+        let clr = ui::input("New color: ", 30, 1);
+        match ColorRepresentation::try_from_color(&clr, &program_state.clr_std) {
+            Ok(clr) => program_state.curr_color = clr,
+            Err(err) => program_state.status = Some(err),
+        }
         None
     });
 
     insert("copy".to_owned(), |program_state, _key| {
-        paste_to_clipboard(
-            &program_state
-                .curr_color
-                .get_formatted_output_clr(&program_state.output_type, program_state.enable_alpha),
-        );
+        let output = program_state
+            .curr_color
+            .get_formatted_output_clr(&program_state.output_type, program_state.enable_alpha);
+        if let Err(err) = program_state.copy(&output) {
+            program_state.status = Some(err);
+        }
         None
     });
 
     insert("copy-raw".to_owned(), |program_state, _key| {
-        paste_to_clipboard(
-            &program_state
-                .curr_color
-                .get_output_clr(&program_state.output_type, program_state.enable_alpha),
-        );
+        let output = program_state
+            .curr_color
+            .get_output_clr(&program_state.output_type, program_state.enable_alpha);
+        if let Err(err) = program_state.copy(&output) {
+            program_state.status = Some(err);
+        }
         None
     });
 
     insert("paste".to_owned(), |program_state, _key| {
-        let mut reader = std::io::stdin();
-        let data = read_clipboard(&mut reader);
-        program_state.curr_color = ColorRepresentation::from_color(&data, &program_state.clr_std);
+        let data = match program_state.paste() {
+            Ok(data) => data,
+            Err(err) => {
+                program_state.status = Some(err);
+                return None;
+            }
+        };
+        match ColorRepresentation::try_from_color(data.trim(), &program_state.clr_std) {
+            Ok(clr) => program_state.curr_color = clr,
+            Err(err) => program_state.status = Some(err),
+        }
+        None
+    });
+
+    insert("select-register".to_owned(), |program_state, _key| {
+        //the register is the next key, a resize while waiting gives an empty string
+        let name = loop {
+            match get_input() {
+                Some(key) if key.is_empty() => continue,
+                Some(key) => break key,
+                None => return None,
+            }
+        };
+        match name.chars().next() {
+            Some(name) if name == '*' || name == '+' || Registers::is_named(name) => {
+                program_state.register = Some(name)
+            }
+            _ => program_state.status = Some(format!("Invalid register: {}", name)),
+        }
         None
     });
 
@@ -312,5 +352,83 @@ pub fn init_keymaps(
         None
     });
 
+    insert("cycle-contrast-algorithm".to_owned(), |program_state, _key| {
+        cls();
+        program_state.contrast_algorithm = program_state.contrast_algorithm.next();
+        None
+    });
+
+    insert("fix-contrast".to_owned(), |program_state, _key| {
+        let Some(bg) = program_state.comparison_colors.first() else {
+            return None;
+        };
+        let (r, g, b) = program_state.curr_color.rgb();
+        let (bg_r, bg_g, bg_b) = bg.rgb();
+        let algorithm = program_state.contrast_algorithm;
+        if let Some(clr) = fix_contrast(
+            [r, g, b],
+            [bg_r, bg_g, bg_b],
+            algorithm.default_target(),
+            &algorithm,
+            &LightnessSpace::OKLCH,
+        ) {
+            program_state.curr_color.modify_rgb((clr[0], clr[1], clr[2]));
+        }
+        None
+    });
+
+    insert("cycle-simulation".to_owned(), |program_state, _key| {
+        cls();
+        program_state.simulation = Deficiency::next(program_state.simulation);
+        None
+    });
+
+    insert("cycle-harmony".to_owned(), |program_state, _key| {
+        cls();
+        program_state.harmony = Harmony::next(program_state.harmony);
+        None
+    });
+
+    insert("cycle-harmony-space".to_owned(), |program_state, _key| {
+        program_state.harmony_space = program_state.harmony_space.next();
+        None
+    });
+
+    insert("randomize".to_owned(), |program_state, _key| {
+        if let Err(err) = randomize(program_state) {
+            program_state.status = Some(err);
+        }
+        None
+    });
+
+    insert("cycle-mix-space".to_owned(), |program_state, _key| {
+        program_state.mix_space = MixSpace::next(program_state.mix_space);
+        None
+    });
+
+    insert("pick-harmony".to_owned(), |program_state, _key| {
+        let colors = harmony_colors(program_state);
+        if colors.is_empty() {
+            program_state.status = Some("No harmony is being shown".to_owned());
+            return None;
+        }
+        let items: Vec<String> = colors
+            .iter()
+            .map(|clr| {
+                format!(
+                    "{}{}",
+                    clr.make_square(),
+                    program_state.output_type.render_output(clr, false)
+                )
+            })
+            .collect();
+        let picked = ui::selection_menu(items.clone(), 30, 1);
+        if let Some(idx) = items.iter().position(|item| *item == picked) {
+            //keeps the alpha of the current color
+            program_state.curr_color.modify_rgb(colors[idx].rgb());
+        }
+        None
+    });
+
     return key_maps;
 }
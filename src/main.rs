@@ -3,10 +3,21 @@ mod tests;
 
 #[macro_use]
 mod math;
+#[macro_use]
+mod screen;
+mod adjust;
 mod cli;
+mod extract;
+mod clipboard;
 mod color_conversions;
+mod color_names;
 mod color_representation;
+mod image;
+mod jpeg;
 mod keymaps;
+mod png;
+mod random;
+mod registers;
 mod ui;
 
 #[macro_use]
@@ -21,50 +32,49 @@ use termios::Termios;
 
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::io::Read;
 use std::os::fd::AsRawFd;
-
-use base64::engine::general_purpose;
-use base64::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 use color_conversions::*;
 
 fn cls() {
     eprint!("\x1b[2J\x1b[0H");
+    screen::invalidate();
 }
 
 fn render_ansi256(selected_item: u8, _square_count: u32) {
-    eprint!(" "); // Clear the console
+    draw!(" "); // Clear the console
 
     for low_nr in 0..16 {
-        eprint!("\x1b[38;5;{}m{:<3} ", low_nr, low_nr); // Print 16 rows of colors
+        draw!("\x1b[38;5;{}m{:<3} ", low_nr, low_nr); // Print 16 rows of colors
     }
-    eprintln!();
+    drawln!();
 
     for i in 0..2 {
         for x in 0..6 {
-            eprint!(" "); // Newline between grids
+            draw!(" "); // Newline between grids
 
             for y in 0..6 {
                 for z in (i * 3)..(3 + i * 3) {
                     let clr = (x + 16) + (6 * y) + (36 * z); // Calculate color index
-                    eprint!("\x1b[38;5;{}m{:<3} ", clr, clr); // Print each color in the current grid square
+                    draw!("\x1b[38;5;{}m{:<3} ", clr, clr); // Print each color in the current grid square
                 }
             }
-            eprintln!(); // Newline between rows within a grid
+            drawln!(); // Newline between rows within a grid
         }
     }
 
-    eprint!(" "); // Clear the console before the final text display
+    draw!(" "); // Clear the console before the final text display
 
     for grey_nr in 232..256 {
         // Print the selected item in grey color
-        eprint!("\x1b[38;5;{}m{:<3} ", grey_nr, grey_nr);
+        draw!("\x1b[38;5;{}m{:<3} ", grey_nr, grey_nr);
     }
 
-    eprintln!(); // Newline before the final text display
-    eprintln!("\x1b[0m"); // Reset color to default (black)
-    eprintln!("\x1b[2K{}", selected_item); // Display the selected item in bright white color on a black background.
+    drawln!(); // Newline before the final text display
+    drawln!("\x1b[0m"); // Reset color to default (black)
+    drawln!("\x1b[2K{}", selected_item); // Display the selected item in bright white color on a black background.
 }
 
 fn ansi256_renderer(
@@ -73,11 +83,10 @@ fn ansi256_renderer(
     square_count: u32,
     _step: f64,
 ) {
-    let mut reader = std::io::stdin();
-    let [_rows, cols] = query_window_area(&mut reader);
-    eprint!("\x1b[0H");
+    let [_rows, cols] = window_area();
+    draw!("\x1b[0H");
     if cols < 97 {
-        eprintln!("\x1b[31mThis terminal is too small to display the ansi picker")
+        drawln!("\x1b[31mThis terminal is too small to display the ansi picker")
     } else {
         render_ansi256(selected_item, square_count);
     }
@@ -95,7 +104,7 @@ fn render_rgb(curr_color: &ColorRepresentation, square_count: u32, step: f64, rg
     colors[modifier_idx] = 0.0;
     //find the label
     let label = ['R', 'G', 'B'][rgb_idx];
-    eprint!("{}", label);
+    draw!("{}", label);
     //create the starting color based on the list of colors
     let mut color = ColorRepresentation::from_color(
         &format!("rgb({},{},{})", colors[0], colors[1], colors[2]),
@@ -103,12 +112,12 @@ fn render_rgb(curr_color: &ColorRepresentation, square_count: u32, step: f64, rg
     );
     for i in 0..square_count {
         //print a square with the correct color
-        eprint!("\x1b[38;2;{}m█", color.toansi(false));
+        draw!("\x1b[38;2;{}m█", color.toansi(false));
         //modifies this slider's color to be i% of 255
         colors[modifier_idx] = (i as f64 / square_count as f64) * 255.0;
         color.modify_rgb((colors[0], colors[1], colors[2]));
     }
-    eprintln!("\x1b[0m");
+    drawln!("\x1b[0m");
     render_carrot_on_current_line(
         ([curr_color.r, curr_color.g, curr_color.b][modifier_idx] / 255.0 * 360.0 / step).floor()
             as usize
@@ -118,9 +127,9 @@ fn render_rgb(curr_color: &ColorRepresentation, square_count: u32, step: f64, rg
 
 fn rgb_renderer(curr_color: &ColorRepresentation, selected_item: u8, square_count: u32, step: f64) {
     for i in 0..=2 {
-        eprint!("\x1b[{};0H", i * 2 + 1);
+        draw!("\x1b[{};0H", i * 2 + 1);
         if selected_item == i {
-            eprint!("\x1b[32m");
+            draw!("\x1b[32m");
         }
         render_rgb(curr_color, square_count, step, i as usize);
     }
@@ -134,17 +143,17 @@ fn render_hsl(curr_color: &ColorRepresentation, square_count: u32, step: f64, hs
     colors[modifier_idx] = 0.0;
     let label = ['H', 'S', 'L'][hsl_idx];
     let modifier_multiplier = [360.0, 100.0, 100.0][hsl_idx];
-    eprint!("{}", label);
+    draw!("{}", label);
     let mut color = ColorRepresentation::from_color(
         &format!("hsl({},{},{})", colors[0], colors[1], colors[2]),
         &ColorNameStandard::W3C,
     );
     for i in 0..square_count {
-        eprint!("\x1b[38;2;{}m█", color.toansi(false));
+        draw!("\x1b[38;2;{}m█", color.toansi(false));
         colors[modifier_idx] = (i as f64 / square_count as f64) * modifier_multiplier;
         color.modify_hsl((colors[0], colors[1], colors[2]));
     }
-    eprintln!("\x1b[0m");
+    drawln!("\x1b[0m");
     render_carrot_on_current_line(
         ([h, s, l][modifier_idx] / modifier_multiplier * 360.0 / step).floor() as usize + 1,
     );
@@ -152,26 +161,26 @@ fn render_hsl(curr_color: &ColorRepresentation, square_count: u32, step: f64, hs
 
 fn hsl_renderer(curr_color: &ColorRepresentation, selected_item: u8, square_count: u32, step: f64) {
     for i in 0..=2 {
-        eprint!("\x1b[{};0H", i * 2 + 1);
+        draw!("\x1b[{};0H", i * 2 + 1);
         if selected_item == i {
-            eprint!("\x1b[32m");
+            draw!("\x1b[32m");
         }
         render_hsl(curr_color, square_count, step, i as usize);
     }
 }
 
 fn render_a(square_count: u32) {
-    eprint!("A");
+    draw!("A");
     let mut sat_color_rep = ColorRepresentation::from_color("#000000", &ColorNameStandard::W3C);
     for i in 0..square_count {
-        eprint!("\x1b[38;2;{}m█", sat_color_rep.toansi(false));
+        draw!("\x1b[38;2;{}m█", sat_color_rep.toansi(false));
         sat_color_rep.modify_hsl((0.0, 0.0, (i as f64 / square_count as f64)))
     }
-    eprintln!("\x1b[0m");
+    drawln!("\x1b[0m");
 }
 
 fn render_carrot_on_current_line(col: usize) {
-    eprintln!("\x1b[2K\x1b[{}C^", col);
+    drawln!("\x1b[2K\x1b[{}C^", col);
 }
 
 fn render_sliders(
@@ -188,7 +197,7 @@ fn render_sliders(
 
     if enable_alpha {
         if selected_item as usize == selected_type.increments().len() - 1 {
-            eprint!("\x1b[32m");
+            draw!("\x1b[32m");
         }
         render_alpha_display(alpha, square_count, step);
     }
@@ -196,66 +205,199 @@ fn render_sliders(
 
 fn render_alpha_display(alpha: u8, square_count: u32, step: f64) {
     render_a(square_count);
-    eprintln!(
+    drawln!(
         "\x1b[2K {}^",
         " ".repeat(((alpha as f64 / 255.0 * 360.0) / step).floor() as usize)
     );
 }
 
-fn render_comparison_colors(program_state: &ProgramState) {
-    for clr in vec![program_state.curr_color]
-        .iter()
-        .chain(program_state.comparison_colors.iter())
+///The current color as it looks over the terminal background when alpha is enabled
+fn shown_color(program_state: &ProgramState) -> ColorRepresentation {
+    let curr = program_state.curr_color;
+    if !program_state.enable_alpha {
+        return curr;
+    }
+    let [r, g, b] = flatten(
+        [curr.r, curr.g, curr.b, curr.a as f64 / 255.0],
+        program_state.terminal_bg,
+    );
+    ColorRepresentation::from_rgb(r, g, b)
+}
+
+///The text next to each square in the comparison section, the current color is first
+fn comparison_lines(program_state: &ProgramState) -> Vec<(ColorRepresentation, String)> {
+    let curr = program_state.curr_color;
+    //contrast is against what is on screen
+    let (r, g, b) = shown_color(program_state).rgb();
+    let mut lines = vec![(
+        shown_color(program_state),
+        program_state
+            .output_type
+            .render_output(&curr, program_state.enable_alpha),
+    )];
+    for clr in &program_state.comparison_colors {
+        let (r2, g2, b2) = clr.rgb();
+        let value = program_state
+            .contrast_algorithm
+            .contrast([r, g, b], [r2, g2, b2]);
+        lines.push((
+            *clr,
+            format!(
+                "{} {} ΔE {:.2}",
+                program_state
+                    .output_type
+                    .render_output(clr, program_state.enable_alpha),
+                program_state.contrast_algorithm.label(value),
+                delta_e([r, g, b], [r2, g2, b2], &DeltaE::CIEDE2000)
+            ),
+        ));
+    }
+    lines
+}
+
+///Width of the squares before the text in the comparison section
+fn comparison_squares_width(program_state: &ProgramState) -> usize {
+    match program_state.simulation {
+        Some(..) => 16,
+        None => 8,
+    }
+}
+
+///The closest named color to the current color, shown beneath its output
+fn nearest_name_line(program_state: &ProgramState) -> String {
+    let (r, g, b) = program_state.curr_color.rgb();
+    match program_state
+        .clr_std
+        .nearest_colors([r, g, b], 1, &DeltaE::CIEDE2000)
+        .first()
     {
-        eprint!("{}", clr.make_square());
-        eprintln!(
-            "{}",
-            program_state
-                .output_type
-                .render_output(clr, program_state.enable_alpha)
-        )
+        Some((name, _, distance)) => format!("~{} (ΔE {:.2})", name, distance),
+        None => String::new(),
+    }
+}
+
+fn render_comparison_colors(program_state: &ProgramState) {
+    for (i, (clr, text)) in comparison_lines(program_state).into_iter().enumerate() {
+        if i == 1 {
+            drawln!(
+                "\x1b[2K{}{}",
+                " ".repeat(comparison_squares_width(program_state)),
+                nearest_name_line(program_state)
+            );
+        }
+        draw!("{}", clr.make_square());
+        if let Some(deficiency) = &program_state.simulation {
+            let simulated = simulate_cvd(clr.integer(), deficiency, 1.0);
+            draw!("{}", ColorRepresentation::from_integer(simulated).make_square());
+        }
+        drawln!("{}", text)
+    }
+    if program_state.comparison_colors.is_empty() {
+        drawln!(
+            "\x1b[2K{}{}",
+            " ".repeat(comparison_squares_width(program_state)),
+            nearest_name_line(program_state)
+        );
+    }
+    if let Some(deficiency) = &program_state.simulation {
+        drawln!("\x1b[2Ksimulating: {}", value_name(deficiency));
     }
 }
 
 fn render_mix_colors(program_state: &ProgramState) {
     let sq_height = 1;
     //this section goes up to where the comparison colors section is
-    let sq_count = program_state.comparison_colors.len() + 1;
-    eprint!("\x1b[{}A", sq_count * sq_height);
+    //the extra 1 is the nearest name line
+    let sq_count = program_state.comparison_colors.len()
+        + 2
+        + program_state.simulation.is_some() as usize;
+    draw!("\x1b[{}A", sq_count * sq_height);
     //go to the right
-    eprint!(
+    draw!(
         "\x1b[{}C",
-        program_state
-            .output_type
-            .render_output(&program_state.curr_color, program_state.enable_alpha)
-            .len()
-            + 9
+        comparison_lines(program_state)
+            .iter()
+            .map(|(_, text)| text.chars().count())
+            .chain([nearest_name_line(program_state).chars().count()])
+            .max()
+            .unwrap_or(0)
+            + comparison_squares_width(program_state)
+            + 1
     );
     //end section
 
-    let mix_space = if let SelectionType::HSL = program_state.selection_type {
-        MixSpace::HSL
-    } else {
-        MixSpace::RGB
-    };
+    let mix_space = mix_space(program_state);
 
-    for clr in vec![program_state.curr_color]
+    for (i, (clr, percent)) in [(program_state.curr_color, 50.0)]
         .iter()
         .chain(program_state.mix_colors.iter())
+        .enumerate()
     {
-        //FIXME: mixed_color should take in a mode such as rgb, or hsl, to mix differently
-        //depending on the mode
-        let mixed_color = ColorRepresentation::from_integer(color_mix(
-            clr.integer(),
-            program_state.curr_color.integer(),
-            0.5,
-            &mix_space,
-        ));
-        let output = program_state.output_type.render_output(&mixed_color, false);
-        let o_width = output.len();
-        eprint!("\x1b[38;2;{}m████████\x1b[0m", mixed_color.toansi(false));
-        eprint!("{}", output);
-        c_control! { down 1, left o_width +  8};
+        let mixed_color = mix_with_alpha(&program_state.curr_color, clr, percent / 100.0, &mix_space);
+        let mut output = program_state.output_type.render_output(&mixed_color, false);
+        if i == 0 && !program_state.mix_colors.is_empty() {
+            output += &format!(" ({})", value_name(&mix_space));
+        }
+        let o_width = output.chars().count();
+        draw!("\x1b[38;2;{}m████████\x1b[0m", mixed_color.toansi(false));
+        draw!("{}", output);
+        draw!("\x1b[1B\x1b[{}D", o_width + 8);
+    }
+}
+
+///The space chosen with cycle-mix-space or --mix-space, otherwise the one matching the input type
+fn mix_space(program_state: &ProgramState) -> MixSpace {
+    match (program_state.mix_space, &program_state.selection_type) {
+        (Some(space), _) => space,
+        (None, SelectionType::HSL) => MixSpace::HSL,
+        (None, _) => MixSpace::RGB,
+    }
+}
+
+///Mixes percent of clr2 into clr1, keeping track of alpha
+fn mix_with_alpha(
+    clr1: &ColorRepresentation,
+    clr2: &ColorRepresentation,
+    percent: f64,
+    mix_space: &MixSpace,
+) -> ColorRepresentation {
+    let rgba = |clr: &ColorRepresentation| [clr.r, clr.g, clr.b, clr.a as f64 / 255.0];
+    let [r, g, b, a] = color_mix(rgba(clr1), rgba(clr2), percent, mix_space);
+    let mut mixed = ColorRepresentation::from_rgb(r.round(), g.round(), b.round());
+    mixed.a = (a * 255.0).round() as u8;
+    mixed
+}
+
+///The colors of the shown harmony, without the current color
+fn harmony_colors(program_state: &ProgramState) -> Vec<ColorRepresentation> {
+    let Some(kind) = &program_state.harmony else {
+        return vec![];
+    };
+    let (r, g, b) = program_state.curr_color.rgb();
+    harmony([r, g, b], kind, &program_state.harmony_space)
+        .into_iter()
+        .skip(1)
+        .map(|rgb| ColorRepresentation::from_rgb(rgb[0], rgb[1], rgb[2]))
+        .collect()
+}
+
+fn render_harmony(program_state: &ProgramState) {
+    let Some(kind) = &program_state.harmony else {
+        return;
+    };
+    drawln!(
+        "\x1b[2K{} ({})",
+        value_name(kind),
+        value_name(&program_state.harmony_space)
+    );
+    //numbered the same as the pick-harmony menu
+    for (i, clr) in harmony_colors(program_state).iter().enumerate() {
+        drawln!(
+            "\x1b[2K{} {}{}",
+            i,
+            clr.make_square(),
+            program_state.output_type.render_output(clr, false)
+        );
     }
 }
 
@@ -275,9 +417,10 @@ fn render_display(program_state: &ProgramState, square_count: u32, step: f64) {
         program_state.enable_alpha,
     );
     render_comparison_colors(program_state);
-    eprint!("\x1b[s");
+    draw!("\x1b[s");
     render_mix_colors(program_state);
-    eprint!("\x1b[u");
+    draw!("\x1b[u");
+    render_harmony(program_state);
 }
 
 #[derive(serde::Deserialize, Clone)]
@@ -285,10 +428,34 @@ struct ConfigOutput {
     order: Vec<String>,
 }
 
+#[derive(serde::Deserialize, Clone)]
+struct ConfigRandom {
+    hue: Option<String>,
+    min_lightness: Option<f64>,
+    max_lightness: Option<f64>,
+    min_chroma: Option<f64>,
+    max_chroma: Option<f64>,
+    min_contrast: Option<f64>,
+}
+
+#[derive(serde::Deserialize, Clone)]
+struct ConfigClipboard {
+    backend: Option<String>,
+    copy: Option<String>,
+    paste: Option<String>,
+    copy_primary: Option<String>,
+    paste_primary: Option<String>,
+    file: Option<std::path::PathBuf>,
+}
+
 #[derive(serde::Deserialize, Clone)]
 struct Config {
     keybinds: Option<HashMap<String, String>>,
     outputs: Option<Vec<HashMap<String, ConfigOutput>>>,
+    colors: Option<HashMap<String, String>>,
+    palettes: Option<HashMap<String, HashMap<String, String>>>,
+    clipboard: Option<ConfigClipboard>,
+    random: Option<ConfigRandom>,
 }
 
 struct ProgramState {
@@ -302,7 +469,23 @@ struct ProgramState {
     output_order: Vec<OutputType>,
     config: Config,
     comparison_colors: Vec<ColorRepresentation>,
-    mix_colors: Vec<ColorRepresentation>,
+    ///Colors to mix with the current color and how much of them to mix in from 0-100
+    mix_colors: Vec<(ColorRepresentation, f64)>,
+    mix_space: Option<MixSpace>,
+    ///What transparent colors are composited over, read from the terminal
+    terminal_bg: [f64; 3],
+    rng: random::Rng,
+    contrast_algorithm: ContrastAlgorithm,
+    simulation: Option<Deficiency>,
+    harmony: Option<Harmony>,
+    harmony_space: LightnessSpace,
+    ///A message (usually an error) shown under the display until the next key press
+    status: Option<String>,
+    ///Built the first time it's needed so that a bad [clipboard] config only matters to the tui
+    clipboard: Option<Box<dyn clipboard::Clipboard>>,
+    registers: registers::Registers,
+    ///The register picked with `"` for the next copy or paste
+    register: Option<char>,
 }
 
 impl ProgramState {
@@ -314,25 +497,62 @@ impl ProgramState {
         output_order: Vec<OutputType>,
         cfg: Config,
         comparison_colors: Vec<ColorRepresentation>,
-        mix_colors: Vec<ColorRepresentation>,
+        mix_colors: Vec<(ColorRepresentation, f64)>,
+        mix_space: Option<MixSpace>,
     ) -> ProgramState {
         ProgramState {
             selected_item: 0,
             selection_type,
             output_type,
             enable_alpha: false,
-            clr_std,
             curr_color: ColorRepresentation::from_color(starting_clr, &clr_std),
+            clr_std,
             output_idx: 0,
             output_order,
+            clipboard: None,
+            registers: registers::Registers::load(get_data_path().join("registers.toml")),
+            register: None,
             config: cfg,
             comparison_colors,
             mix_colors,
+            mix_space,
+            terminal_bg: [0.0, 0.0, 0.0],
+            rng: random::Rng::new(random::seed_from_time()),
+            contrast_algorithm: ContrastAlgorithm::WCAG,
+            simulation: None,
+            harmony: None,
+            harmony_space: LightnessSpace::HSL,
+            status: None,
         }
     }
 
     // fn from_args(args: &Args) -> Self {}
 
+    fn clipboard(&mut self) -> Result<&dyn clipboard::Clipboard, String> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(clipboard::from_config(self.config.clipboard.as_ref())?);
+        }
+        Ok(self.clipboard.as_deref().unwrap())
+    }
+
+    ///Copies to the selected register, or the clipboard if none was selected
+    ///`*` is the primary selection and `+` the clipboard
+    fn copy(&mut self, data: &str) -> Result<(), String> {
+        match self.register.take() {
+            None | Some('+') => self.clipboard()?.copy(data, clipboard::Selection::Clipboard),
+            Some('*') => self.clipboard()?.copy(data, clipboard::Selection::Primary),
+            Some(name) => self.registers.set(name, data),
+        }
+    }
+
+    fn paste(&mut self) -> Result<String, String> {
+        match self.register.take() {
+            None | Some('+') => self.clipboard()?.paste(clipboard::Selection::Clipboard),
+            Some('*') => self.clipboard()?.paste(clipboard::Selection::Primary),
+            Some(name) => self.registers.get(name).cloned(),
+        }
+    }
+
     fn next_output(&mut self) {
         self.output_idx = (self.output_idx + 1) % self.output_order.len();
         match self.output_type {
@@ -423,9 +643,7 @@ impl SelectionType {
                 ]);
             }
             Self::ANSI256 => {
-                let mut reader = std::io::stdin();
-                let low_rgb = get_ansi_30_and_90(&mut reader);
-                let (r, g, b) = ansi2562rgb(new_value as u8, &low_rgb);
+                let (r, g, b) = ansi2562rgb(new_value as u8, terminal_palette());
                 program_state
                     .curr_color
                     .modify_rgb((r as f64, g as f64, b as f64));
@@ -521,11 +739,44 @@ impl OutputType {
     }
 }
 
-fn read_osc_response(reader: &mut std::io::Stdin, end_byte: u8) -> String {
+///How long to wait for the terminal to answer a query before giving up
+const QUERY_TIMEOUT_MS: i32 = 500;
+
+///Waits until there is something to read on the terminal, returns false on timeout
+fn wait_for_input(timeout_ms: i32) -> bool {
+    let mut fds = libc::pollfd {
+        fd: 0,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    loop {
+        let ready = unsafe { libc::poll(&mut fds, 1, timeout_ms) };
+        //a resize while waiting for a response shouldn't count as a timeout
+        if ready < 0 && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+            continue;
+        }
+        return ready > 0;
+    }
+}
+
+///Reads straight from the terminal's fd, everything that reads the terminal has to go through
+///this since Stdin's buffer would hide pending bytes from poll
+///returns the number of bytes read, 0 on EOF and -1 on errors like read(2)
+pub fn read_terminal(buf: &mut [u8]) -> isize {
+    unsafe { libc::read(0, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) }
+}
+
+///returns None if the terminal does not respond within QUERY_TIMEOUT_MS
+fn read_osc_response(end_byte: u8) -> Option<String> {
     let mut result_str = String::new();
     let mut b = [0; 1];
     loop {
-        reader.read_exact(&mut b).unwrap();
+        if !wait_for_input(QUERY_TIMEOUT_MS) {
+            return None;
+        }
+        if read_terminal(&mut b) != 1 {
+            return None;
+        }
         if b[0] == end_byte {
             break;
         }
@@ -535,83 +786,191 @@ fn read_osc_response(reader: &mut std::io::Stdin, end_byte: u8) -> String {
         }
         result_str += &String::from(b[0] as char);
     }
-    return result_str;
-}
-
-fn query_window_area(reader: &mut std::io::Stdin) -> [i32; 2] {
-    eprint!("\x1b[18t");
-    let area = read_osc_response(reader, b't');
-    let mut split = area.split(";");
-    let rows = split.nth(1).unwrap().parse().unwrap_or(80);
-    let cols = split.next().unwrap().parse().unwrap_or(24);
-    return [rows, cols];
-}
-
-fn read_ansi_color(reader: &mut std::io::Stdin, clr_num: u8) -> [u8; 3] {
-    eprint!("\x1b]4;{};?\x07", clr_num);
-    let clr_buf = read_osc_response(reader, 7);
-    //parses out garbage, gives us rr/gg/bb
-    let data = &clr_buf
-        .as_str()
-        .split(";")
-        .nth(2)
-        .unwrap()
-        .split(":")
-        .nth(1)
-        .unwrap();
-    let mut hexes = data.split("/");
-    let r = &hexes.next().unwrap()[0..2];
-    let g = &hexes.next().unwrap()[0..2];
-    let b = &hexes.next().unwrap()[0..2];
-    [
-        u8::from_str_radix(r, 16).unwrap_or(0),
-        u8::from_str_radix(g, 16).unwrap_or(0),
-        u8::from_str_radix(b, 16).unwrap_or(0),
-    ]
+    //replies forwarded by tmux or screen can come back wrapped the same way the query was sent
+    let unwrapped = result_str
+        .strip_prefix("\x1bPtmux;")
+        .or(result_str.strip_prefix("\x1bP"))
+        .map(|inner| inner.replace("\x1b\x1b", "\x1b"));
+    if let Some(inner) = unwrapped {
+        result_str = inner.trim_end_matches('\x1b').to_owned();
+        //the closing ST of the DCS is still waiting to be read
+        for _ in 0..2 {
+            if !wait_for_input(QUERY_TIMEOUT_MS) || read_terminal(&mut b) != 1 {
+                break;
+            }
+        }
+    }
+    return Some(result_str);
 }
 
-fn get_ansi_30_and_90(reader: &mut std::io::Stdin) -> Vec<String> {
-    let mut data = Vec::with_capacity(16);
-    for i in 0..16 {
-        let clr = read_ansi_color(reader, i);
-        data.push(format!("#{}{}{}", clr[0], clr[1], clr[2]));
+///Wraps an escape sequence in DCS passthrough so that tmux and screen send it on to the outer
+///terminal instead of swallowing it, tmux needs `set -g allow-passthrough on`
+fn passthrough(seq: &str) -> String {
+    if std::env::var_os("TMUX").is_some() {
+        format!("\x1bPtmux;{}\x1b\\", seq.replace("\x1b", "\x1b\x1b"))
+    } else if std::env::var_os("STY").is_some() {
+        //screen limits how long a DCS string can be, so long sequences (OSC 52) are split up
+        seq.as_bytes()
+            .chunks(768)
+            .map(|chunk| format!("\x1bP{}\x1b\\", String::from_utf8_lossy(chunk)))
+            .collect()
+    } else {
+        seq.to_owned()
     }
-    return data;
 }
 
-///clr can be 10 or 11
-fn query_color(clr: u8, reader: &mut std::io::Stdin) -> String {
-    eprint!("\x1b]{};?\x07", clr);
-    let clr_buf = read_osc_response(reader, 7);
-    //parses out garbage, gives us rr/gg/bb
-    let data = &clr_buf
-        .as_str()
-        .split(";")
-        .nth(1)
-        .unwrap()
-        .split(":")
-        .nth(1)
-        .unwrap();
+///Sends `ESC ] body BEL` to the terminal
+fn send_osc(body: &str) {
+    eprint!("{}", passthrough(&format!("\x1b]{}\x07", body)));
+}
+
+///Set by the SIGWINCH handler, the main loop picks it up and redraws
+static WINDOW_RESIZED: AtomicBool = AtomicBool::new(false);
+static WINDOW_AREA: Mutex<[i32; 2]> = Mutex::new([24, 80]);
+///The read and write ends of a pipe the SIGWINCH handler writes to, so that get_input wakes up
+///even if the resize happened after the main loop checked WINDOW_RESIZED, -1 until it's made
+static RESIZE_PIPE: [AtomicI32; 2] = [AtomicI32::new(-1), AtomicI32::new(-1)];
+
+extern "C" fn on_window_resize(_signal: libc::c_int) {
+    WINDOW_RESIZED.store(true, Ordering::SeqCst);
+    let fd = RESIZE_PIPE[1].load(Ordering::SeqCst);
+    if fd >= 0 {
+        unsafe { libc::write(fd, [1u8].as_ptr() as *const libc::c_void, 1) };
+    }
+}
+
+///Empties the resize pipe, returns true if there was anything in it
+fn drain_resize_pipe() -> bool {
+    let fd = RESIZE_PIPE[0].load(Ordering::SeqCst);
+    let mut buf = [0u8; 64];
+    let mut drained = false;
+    //the pipe is non blocking so this stops once it's empty
+    while fd >= 0
+        && unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) } > 0
+    {
+        drained = true;
+    }
+    drained
+}
+
+fn window_area_from_ioctl() -> [i32; 2] {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if unsafe { libc::ioctl(0, libc::TIOCGWINSZ, &mut size) } == 0 && size.ws_col > 0 {
+        return [size.ws_row as i32, size.ws_col as i32];
+    }
+    [24, 80]
+}
+
+fn update_window_area() {
+    *WINDOW_AREA.lock().unwrap() = window_area_from_ioctl();
+}
+
+///[rows, cols] as of the last resize
+fn window_area() -> [i32; 2] {
+    *WINDOW_AREA.lock().unwrap()
+}
+
+///Reads the window size and keeps it up to date with SIGWINCH
+fn watch_window_area() {
+    update_window_area();
+    unsafe {
+        let mut fds = [-1; 2];
+        if libc::pipe(fds.as_mut_ptr()) == 0 {
+            for fd in fds {
+                libc::fcntl(fd, libc::F_SETFL, libc::fcntl(fd, libc::F_GETFL) | libc::O_NONBLOCK);
+                libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            }
+            RESIZE_PIPE[0].store(fds[0], Ordering::SeqCst);
+            RESIZE_PIPE[1].store(fds[1], Ordering::SeqCst);
+        }
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction =
+            on_window_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+        //no SA_RESTART so that a resize also interrupts reads outside of get_input
+        libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut());
+    }
+}
+
+///Parses the rr/gg/bb out of an `rgb:rrrr/gggg/bbbb` color in an OSC response
+///`field` is the ; separated field that the color is in
+fn parse_osc_color(response: &str, field: usize) -> Option<[u8; 3]> {
+    let data = response.split(";").nth(field)?.split(":").nth(1)?;
     let mut hexes = data.split("/");
-    let r = &hexes.next().unwrap()[0..2];
-    let g = &hexes.next().unwrap()[0..2];
-    let b = &hexes.next().unwrap()[0..2];
-    return format!("#{}{}{}", r, g, b);
+    let mut clr = [0; 3];
+    for channel in clr.iter_mut() {
+        *channel = u8::from_str_radix(hexes.next()?.get(0..2)?, 16).ok()?;
+    }
+    Some(clr)
+}
+
+fn read_ansi_color(clr_num: u8) -> Option<[u8; 3]> {
+    send_osc(&format!("4;{};?", clr_num));
+    let clr_buf = read_osc_response(7)?;
+    parse_osc_color(&clr_buf, 2)
 }
 
-fn paste_to_clipboard(data: &str) {
-    let b64 = general_purpose::STANDARD.encode(data);
-    eprint!("\x1b]52;c;{}\x07", b64);
+///The default xterm colors, used when the terminal does not answer palette queries
+const XTERM_PALETTE: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0xcd, 0x00, 0x00],
+    [0x00, 0xcd, 0x00],
+    [0xcd, 0xcd, 0x00],
+    [0x00, 0x00, 0xee],
+    [0xcd, 0x00, 0xcd],
+    [0x00, 0xcd, 0xcd],
+    [0xe5, 0xe5, 0xe5],
+    [0x7f, 0x7f, 0x7f],
+    [0xff, 0x00, 0x00],
+    [0x00, 0xff, 0x00],
+    [0xff, 0xff, 0x00],
+    [0x5c, 0x5c, 0xff],
+    [0xff, 0x00, 0xff],
+    [0x00, 0xff, 0xff],
+    [0xff, 0xff, 0xff],
+];
+
+fn get_ansi_30_and_90() -> Vec<String> {
+    let mut data = Vec::with_capacity(16);
+    let mut answered = true;
+    for i in 0..16 {
+        //if the terminal didn't answer once it's not going to answer the rest
+        let clr = if answered {
+            read_ansi_color(i)
+        } else {
+            None
+        };
+        answered = clr.is_some();
+        let clr = clr.unwrap_or(XTERM_PALETTE[i as usize]);
+        data.push(format!("#{:02x}{:02x}{:02x}", clr[0], clr[1], clr[2]));
+    }
+    return data;
 }
 
-fn read_clipboard(reader: &mut std::io::Stdin) -> String {
-    eprintln!("\x1b]52;c;?\x07");
+static TERMINAL_PALETTE: OnceLock<Vec<String>> = OnceLock::new();
 
-    let clip_buf = read_osc_response(reader, 7);
+///The 16 terminal colors as #rrggbb, the terminal is only queried the first time this is called
+fn terminal_palette() -> &'static Vec<String> {
+    TERMINAL_PALETTE.get_or_init(|| get_ansi_30_and_90())
+}
 
-    let clip_data = clip_buf.split(";").nth(2).unwrap();
+///clr can be 10 or 11
+///returns None if the terminal does not support the query
+fn query_color(clr: u8) -> Option<String> {
+    send_osc(&format!("{};?", clr));
+    let clr_buf = read_osc_response(7)?;
+    let [r, g, b] = parse_osc_color(&clr_buf, 1)?;
+    return Some(format!("#{:02x}{:02x}{:02x}", r, g, b));
+}
 
-    return String::from_utf8(general_purpose::STANDARD.decode(clip_data).unwrap()).unwrap();
+///Prints msg and exits, turning echo and canonical mode back on in case setup_term was called
+fn exit_with_error(msg: &str) -> ! {
+    if let Ok(mut tios) = Termios::from_fd(0) {
+        tios.c_lflag |= termios::ICANON | termios::ECHO;
+        let _ = termios::tcsetattr(0, termios::TCSANOW, &tios);
+    }
+    eprintln!("{}", msg);
+    unsafe { exit(1) }
 }
 
 //returns oldtermios, newtermios
@@ -633,6 +992,13 @@ fn setup_term() -> (termios::Termios, termios::Termios) {
             tios.c_lflag &= !(termios::ICANON | termios::ECHO);
             termios::tcsetattr(0, termios::TCSANOW, &tios).unwrap();
 
+            //a panic would otherwise leave the terminal without echo
+            let default_hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                let _ = termios::tcsetattr(0, termios::TCSANOW, &tios_initial);
+                default_hook(info);
+            }));
+
             return (tios_initial, tios);
         }
     }
@@ -645,67 +1011,344 @@ fn close_term(initial_ios: &termios::Termios) {
 ///Reads up to 32 bytes from the terminal stream,
 ///returns it assuming that it is a valid utf-8
 ///representation of the user's input
-fn get_input(reader: &mut std::io::Stdin) -> String {
+///returns an empty string if the wait was interrupted by a resize and None once the terminal is
+///closed
+fn get_input() -> Option<String> {
     let mut buf = [0; 32];
 
-    let bytes_read = reader.read(&mut buf).unwrap();
+    //wait on the terminal and the resize pipe together, a resize between the main loop checking
+    //WINDOW_RESIZED and this wait still wakes it up
+    let mut fds = [
+        libc::pollfd {
+            fd: 0,
+            events: libc::POLLIN,
+            revents: 0,
+        },
+        libc::pollfd {
+            fd: RESIZE_PIPE[0].load(Ordering::SeqCst),
+            events: libc::POLLIN,
+            revents: 0,
+        },
+    ];
+    let ready = unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) };
+    let interrupted =
+        ready < 0 && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted;
+    if interrupted || drain_resize_pipe() {
+        return Some(String::new());
+    }
+
+    let bytes_read = read_terminal(&mut buf);
+    if bytes_read < 0 {
+        let interrupted = std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted;
+        return interrupted.then(String::new);
+    }
+    if bytes_read == 0 {
+        return None;
+    }
+
+    let data = String::from_utf8_lossy(&buf[0..bytes_read as usize]).to_string();
+    //a reply to a query that came after read_osc_response gave up on it, not something the user
+    //typed (alt+] and alt+P are only the 2 bytes)
+    if (data.starts_with("\x1b]") || data.starts_with("\x1bP")) && data.len() > 2 {
+        if !data.ends_with('\x07') && !data.ends_with("\x1b\\") {
+            //the rest of it is still on its way
+            let _ = read_osc_response(7);
+        }
+        return Some(String::new());
+    }
+    Some(data)
+}
 
-    String::from_utf8(buf[0..bytes_read].to_vec()).unwrap()
+///Splits a `color:percent` pair, the percent defaults to 50
+fn parse_color_and_percent(clr: &str) -> Result<(&str, f64), String> {
+    let (clr_name, percent) = clr.split_once(":").unwrap_or((clr, "50"));
+    let percent = percent
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid percent for {}: {}", clr_name, percent))?;
+    Ok((clr_name, percent))
 }
 
 fn mix(mixing_args: &MixArgs, clr_std: &ColorNameStandard) -> Vec<ColorRepresentation> {
-    let clr1 = ColorRepresentation::from_color(&mixing_args.color, clr_std).integer();
+    let clr1 = ColorRepresentation::from_color(&mixing_args.color, clr_std);
     let mut clrs = vec![];
     for clr in &mixing_args.with {
-        let mut clr_and_percent = clr.split(":");
-        let clr_name = clr_and_percent.next().unwrap();
-        let percent = clr_and_percent
-            .next()
-            .unwrap_or("50")
-            .parse::<f32>()
-            .unwrap() as f64;
-        clrs.push(ColorRepresentation::from_integer(color_mix(
-            clr1,
-            ColorRepresentation::from_color(clr_name, clr_std).integer(),
+        let (clr_name, percent) =
+            parse_color_and_percent(clr).unwrap_or_else(|err| exit_with_error(&err));
+        let mut mixed = mix_with_alpha(
+            &clr1,
+            &ColorRepresentation::from_color(clr_name, clr_std),
             percent / 100.0,
-            &MixSpace::RGB,
-        )))
+            &mixing_args.space,
+        );
+        if let Some(deficiency) = &mixing_args.simulate {
+            let alpha = mixed.a;
+            mixed = ColorRepresentation::from_integer(simulate_cvd(
+                mixed.integer(),
+                deficiency,
+                mixing_args.severity,
+            ));
+            mixed.a = alpha;
+        }
+        clrs.push(mixed)
     }
     return clrs;
 }
 
-fn convert_action(conversion: ConvertArgs, curr_color: &ColorRepresentation) {
-    println!(
-        "{}",
-        match conversion.to {
-            RequestedOutputType::HSL => OutputType::HSL.render_output(curr_color, conversion.alpha),
-            RequestedOutputType::RGB => OutputType::RGB.render_output(curr_color, conversion.alpha),
-            RequestedOutputType::HEX => OutputType::HEX.render_output(curr_color, conversion.alpha),
-            _ => OutputType::CUSTOM(conversion.fmt.unwrap_or("%xD".to_string()))
-                .render_output(curr_color, conversion.alpha),
+fn convert_action(
+    conversion: ConvertArgs,
+    input: &str,
+    curr_color: &ColorRepresentation,
+    json: bool,
+) {
+    let output = match conversion.to {
+        RequestedOutputType::HSL => OutputType::HSL.render_output(curr_color, conversion.alpha),
+        RequestedOutputType::RGB => OutputType::RGB.render_output(curr_color, conversion.alpha),
+        RequestedOutputType::HEX => OutputType::HEX.render_output(curr_color, conversion.alpha),
+        _ => OutputType::CUSTOM(conversion.fmt.unwrap_or("%xD".to_string()))
+            .render_output(curr_color, conversion.alpha),
+    };
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "input": input,
+                "color": curr_color.to_json(),
+                "output": output,
+            })
+        );
+        return;
+    }
+    println!("{}", output);
+}
+
+fn apca_polarity(lc: f64) -> &'static str {
+    if lc >= 0.0 {
+        "dark-on-light"
+    } else {
+        "light-on-dark"
+    }
+}
+
+///Quotes field as RFC 4180 requires if it contains a comma, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+///The contrast of every color against every other one, indexed as matrix[fg][bg]
+fn contrast_matrix(colors: &[ColorRepresentation], algorithm: &ContrastAlgorithm) -> Vec<Vec<f64>> {
+    colors
+        .iter()
+        .map(|fg| {
+            let (r, g, b) = fg.rgb();
+            colors
+                .iter()
+                .map(|bg| {
+                    let (bg_r, bg_g, bg_b) = bg.rgb();
+                    algorithm.contrast([r, g, b], [bg_r, bg_g, bg_b])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+///Returns false if any pair of different colors is below `args.min`
+fn contrast_matrix_action(args: &ContrastArgs, program_state: &ProgramState, json: bool) -> bool {
+    let algorithm = args.algorithm.unwrap_or(ContrastAlgorithm::WCAG);
+    let colors: Vec<ColorRepresentation> = args
+        .colors
+        .iter()
+        .map(|clr| ColorRepresentation::from_color(clr, &program_state.clr_std))
+        .collect();
+    let matrix = contrast_matrix(&colors, &algorithm);
+    let all_passed = matrix.iter().enumerate().all(|(i, row)| {
+        row.iter()
+            .enumerate()
+            .all(|(j, value)| i == j || algorithm.passes(*value, args.min.unwrap_or(0.0)))
+    });
+
+    if json {
+        let json_colors: Vec<_> = args
+            .colors
+            .iter()
+            .zip(colors.iter())
+            .map(|(input, clr)| serde_json::json!({ "input": input, "color": clr.to_json() }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "algorithm": value_name(&algorithm),
+                "colors": json_colors,
+                "matrix": matrix,
+                "pass": all_passed,
+            })
+        );
+    } else if args.csv {
+        let labels: Vec<String> = args.colors.iter().map(|clr| csv_field(clr)).collect();
+        println!(",{}", labels.join(","));
+        for (label, row) in labels.iter().zip(matrix.iter()) {
+            let values: Vec<String> = row.iter().map(|v| format!("{:.2}", v)).collect();
+            println!("{},{}", label, values.join(","));
         }
-    );
+    } else {
+        const CELL_WIDTH: usize = 10;
+        let label_width = args.colors.iter().map(|c| c.len()).max().unwrap_or(0);
+        print!("{:label_width$} ", "");
+        for input in &args.colors {
+            print!("{:^CELL_WIDTH$.CELL_WIDTH$}", input);
+        }
+        println!();
+        for (i, fg) in colors.iter().enumerate() {
+            print!("{:label_width$} ", args.colors[i]);
+            for (j, bg) in colors.iter().enumerate() {
+                let mut label = algorithm.label(matrix[i][j]);
+                //mark failing pairs so they're visible even when the colors make it unreadable
+                if i != j && !algorithm.passes(matrix[i][j], args.min.unwrap_or(0.0)) {
+                    label = format!("!{}", label);
+                }
+                print!(
+                    "\x1b[38;2;{};48;2;{}m{:^CELL_WIDTH$}\x1b[0m",
+                    fg.toansi(false),
+                    bg.toansi(false),
+                    label
+                );
+            }
+            println!();
+        }
+    }
+    all_passed
 }
 
-fn contrast_action(args: &ContrastArgs, program_state: &ProgramState) {
+///Returns false if any of the contrasts are below `args.min`
+fn contrast_action(args: &ContrastArgs, program_state: &ProgramState, json: bool) -> bool {
+    if args.matrix {
+        return contrast_matrix_action(args, program_state, json);
+    }
     let colors = &args.colors;
+    let algorithm = args.algorithm.unwrap_or(ContrastAlgorithm::WCAG);
     let initial_clr = program_state.curr_color.rgb();
     let clr1 = [initial_clr.0, initial_clr.1, initial_clr.2];
-    println!("{}", program_state.curr_color.make_square());
+    let mut results = vec![];
+    let mut all_passed = true;
+    if !json {
+        println!("{}", program_state.curr_color.make_square());
+    }
     for color in colors {
         let repr = ColorRepresentation::from_color(&color, &program_state.clr_std);
         let rgb = repr.rgb();
         let clr2 = [rgb.0, rgb.1, rgb.2];
+        let (text, bg) = if args.reverse {
+            (clr2, clr1)
+        } else {
+            (clr1, clr2)
+        };
+        let ratio = color_conversions::contrast(text, bg);
+        let lc = apca_contrast(text, bg);
+        let value = algorithm.contrast(text, bg);
+        let passed = algorithm.passes(value, args.min.unwrap_or(0.0));
+        all_passed &= passed;
+        let fixed = if args.fix {
+            fix_contrast(
+                text,
+                bg,
+                args.min.unwrap_or(algorithm.default_target()),
+                &algorithm,
+                &args.fix_space.unwrap_or(LightnessSpace::OKLCH),
+            )
+            .map(|[r, g, b]| ColorRepresentation::from_rgb(r, g, b))
+        } else {
+            None
+        };
+        if json {
+            let wcag: serde_json::Map<String, serde_json::Value> = wcag_report(ratio)
+                .iter()
+                .map(|(name, pass)| (name.to_string(), serde_json::Value::Bool(*pass)))
+                .collect();
+            results.push(serde_json::json!({
+                "input": color,
+                "color": repr.to_json(),
+                "ratio": ratio,
+                "wcag": wcag,
+                "apca": { "lc": lc, "polarity": apca_polarity(lc) },
+                "pass": passed,
+                "fix": fixed.map(|clr| clr.to_json()),
+            }));
+            continue;
+        }
+        match algorithm {
+            ContrastAlgorithm::WCAG => {
+                let report_text: Vec<String> = wcag_report(ratio)
+                    .iter()
+                    .map(|(name, pass)| {
+                        format!("{}: {}", name, if *pass { "PASS" } else { "FAIL" })
+                    })
+                    .collect();
+                print!("{}: {:.2} {}", repr.make_square(), ratio, report_text.join(", "));
+            }
+            ContrastAlgorithm::APCA => {
+                print!("{}: {} ({})", repr.make_square(), algorithm.label(lc), apca_polarity(lc));
+            }
+        }
+        if !passed {
+            print!(" \x1b[31m(below {})\x1b[0m", args.min.unwrap());
+        }
+        println!();
+        if args.fix {
+            match fixed {
+                Some(clr) => println!(
+                    "  fix: {}{}",
+                    clr.make_square(),
+                    program_state.output_type.render_output(&clr, false)
+                ),
+                None => println!("  fix: no passing color"),
+            }
+        }
+    }
+    if json {
         println!(
-            "{}: {}",
-            repr.make_square(),
-            color_conversions::contrast(clr1, clr2)
+            "{}",
+            serde_json::json!({
+                "color": program_state.curr_color.to_json(),
+                "contrasts": results,
+                "pass": all_passed,
+            })
         );
     }
+    all_passed
 }
 
-fn mix_action(args: &MixArgs, program_state: &ProgramState) {
+fn mix_action(args: &MixArgs, program_state: &ProgramState, json: bool) {
     let colors = mix(&args, &program_state.clr_std);
+    if json {
+        let results: Vec<_> = args
+            .with
+            .iter()
+            .zip(colors.iter())
+            .map(|(with, color)| {
+                //mix already checked that these parse
+                let (clr_name, percent) = parse_color_and_percent(with).unwrap();
+                serde_json::json!({
+                    "with": clr_name,
+                    "percent": percent,
+                    "color": color.to_json(),
+                    "output": program_state.output_type.render_output(color, false),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "input": args.color,
+                "color": ColorRepresentation::from_color(&args.color, &program_state.clr_std)
+                    .to_json(),
+                "results": results,
+            })
+        );
+        return;
+    }
     for color in colors {
         if args.preview {
             println!("{}", color.make_square());
@@ -714,12 +1357,541 @@ fn mix_action(args: &MixArgs, program_state: &ProgramState) {
     }
 }
 
-fn invert_action(args: &InvertArgs, program_state: &ProgramState) {
+fn invert_action(args: &InvertArgs, program_state: &ProgramState, json: bool) {
     let clr = ColorRepresentation::from_integer(invert(program_state.curr_color.integer()));
+    let output = program_state.output_type.render_output(&clr, false);
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "color": program_state.curr_color.to_json(),
+                "result": clr.to_json(),
+                "output": output,
+            })
+        );
+        return;
+    }
     if args.preview {
         println!("{}", clr.make_square());
     }
-    println!("{}", program_state.output_type.render_output(&clr, false));
+    println!("{}", output);
+}
+
+fn simulate_action(args: &SimulateArgs, program_state: &ProgramState, json: bool) {
+    let colors: Vec<(String, ColorRepresentation)> = if args.colors.is_empty() {
+        vec![(program_state.curr_color.tohex(false), program_state.curr_color)]
+    } else {
+        args.colors
+            .iter()
+            .map(|clr| {
+                (
+                    clr.to_owned(),
+                    ColorRepresentation::from_color(clr, &program_state.clr_std),
+                )
+            })
+            .collect()
+    };
+    let mut results = vec![];
+    for (input, clr) in colors {
+        let simulated = ColorRepresentation::from_integer(simulate_cvd(
+            clr.integer(),
+            &args.kind,
+            args.severity,
+        ));
+        let output = program_state.output_type.render_output(&simulated, false);
+        if json {
+            results.push(serde_json::json!({
+                "input": input,
+                "color": clr.to_json(),
+                "result": simulated.to_json(),
+                "output": output,
+            }));
+            continue;
+        }
+        if args.preview {
+            print!("{}{}", clr.make_square(), simulated.make_square());
+        }
+        println!("{}", output);
+    }
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "type": value_name(&args.kind),
+                "severity": args.severity,
+                "results": results,
+            })
+        );
+    }
+}
+
+fn diff_action(args: &DiffArgs, program_state: &ProgramState, json: bool) {
+    let a = ColorRepresentation::from_color(&args.a, &program_state.clr_std);
+    let b = ColorRepresentation::from_color(&args.b, &program_state.clr_std);
+    let (r1, g1, b1) = a.rgb();
+    let (r2, g2, b2) = b.rgb();
+    let methods = match args.method {
+        Some(method) => vec![method],
+        None => DeltaE::ALL.to_vec(),
+    };
+    let results: Vec<(DeltaE, f64)> = methods
+        .iter()
+        .map(|method| (*method, delta_e([r1, g1, b1], [r2, g2, b2], method)))
+        .collect();
+    if json {
+        let delta_es: serde_json::Map<String, serde_json::Value> = results
+            .iter()
+            .map(|(method, value)| (value_name(method), (*value).into()))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "a": { "input": args.a, "color": a.to_json() },
+                "b": { "input": args.b, "color": b.to_json() },
+                "delta_e": delta_es,
+            })
+        );
+        return;
+    }
+    println!("{}{}", a.make_square(), b.make_square());
+    for (method, value) in results {
+        println!("{}: {:.4}", value_name(&method), value);
+    }
+}
+
+fn name_action(args: &NameArgs, program_state: &ProgramState, json: bool) {
+    let clr = match &args.color {
+        Some(clr) => ColorRepresentation::from_color(clr, &program_state.clr_std),
+        None => program_state.curr_color,
+    };
+    let (r, g, b) = clr.rgb();
+    let names = program_state.clr_std.nearest_colors(
+        [r, g, b],
+        args.top,
+        &args.method.unwrap_or(DeltaE::CIEDE2000),
+    );
+    if json {
+        let results: Vec<_> = names
+            .iter()
+            .map(|(name, rgb, distance)| {
+                serde_json::json!({
+                    "name": name,
+                    "color": ColorRepresentation::from_rgb(
+                        rgb[0] as f64,
+                        rgb[1] as f64,
+                        rgb[2] as f64
+                    )
+                    .to_json(),
+                    "delta_e": distance,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "color": clr.to_json(),
+                "names": results,
+            })
+        );
+        return;
+    }
+    for (name, rgb, distance) in names {
+        let named = ColorRepresentation::from_rgb(rgb[0] as f64, rgb[1] as f64, rgb[2] as f64);
+        println!(
+            "{}{}: {} (ΔE {:.2})",
+            named.make_square(),
+            name,
+            program_state.output_type.render_output(&named, false),
+            distance
+        );
+    }
+}
+
+///The name of an enum value as it's written on the command line, eg: split-complementary
+fn value_name<T: clap::ValueEnum>(value: &T) -> String {
+    value.to_possible_value().unwrap().get_name().to_owned()
+}
+
+fn harmony_action(args: &HarmonyArgs, program_state: &ProgramState, json: bool) {
+    let clr = match &args.color {
+        Some(clr) => ColorRepresentation::from_color(clr, &program_state.clr_std),
+        None => program_state.curr_color,
+    };
+    let (r, g, b) = clr.rgb();
+    let space = args.space.unwrap_or(LightnessSpace::HSL);
+    let kinds = match args.kind {
+        Some(kind) => vec![kind],
+        None => Harmony::ALL.to_vec(),
+    };
+    let mut results = vec![];
+    for kind in kinds {
+        let colors: Vec<ColorRepresentation> = harmony([r, g, b], &kind, &space)
+            .into_iter()
+            .map(|rgb| ColorRepresentation::from_rgb(rgb[0], rgb[1], rgb[2]))
+            .collect();
+        if json {
+            results.push(serde_json::json!({
+                "kind": value_name(&kind),
+                "colors": colors.iter().map(|clr| clr.to_json()).collect::<Vec<_>>(),
+            }));
+            continue;
+        }
+        if args.kind.is_none() {
+            println!("{}:", value_name(&kind));
+        }
+        for clr in colors {
+            if args.preview {
+                print!("{}", clr.make_square());
+            }
+            println!("{}", program_state.output_type.render_output(&clr, false));
+        }
+    }
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "color": clr.to_json(),
+                "space": value_name(&space),
+                "harmonies": results,
+            })
+        );
+    }
+}
+
+///The name of each step in a scale, tonal scales use tailwind's 50-950 and the others how far
+///the color is mixed in percent
+fn scale_labels(steps: usize, kind: &ScaleKind) -> Vec<usize> {
+    const TAILWIND: [usize; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+    match kind {
+        ScaleKind::Tonal if steps == TAILWIND.len() => TAILWIND.to_vec(),
+        ScaleKind::Tonal if steps == 1 => vec![500],
+        ScaleKind::Tonal => (0..steps)
+            .map(|i| 50 + (900.0 * i as f64 / (steps - 1) as f64 / 10.0).round() as usize * 10)
+            .collect(),
+        _ => (0..steps)
+            .map(|i| (100.0 * i as f64 / steps as f64).round() as usize)
+            .collect(),
+    }
+}
+
+fn scale_action(args: &ScaleArgs, program_state: &ProgramState, json: bool) {
+    let clr = match &args.color {
+        Some(clr) => ColorRepresentation::from_color(clr, &program_state.clr_std),
+        None => program_state.curr_color,
+    };
+    let (r, g, b) = clr.rgb();
+    let colors: Vec<ColorRepresentation> = scale([r, g, b], args.steps, &args.kind)
+        .into_iter()
+        .map(|rgb| ColorRepresentation::from_rgb(rgb[0], rgb[1], rgb[2]))
+        .collect();
+    let labels = scale_labels(args.steps, &args.kind);
+    if json {
+        let steps: Vec<_> = labels
+            .iter()
+            .zip(&colors)
+            .map(|(label, clr)| serde_json::json!({ "label": label, "color": clr.to_json() }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "color": clr.to_json(),
+                "kind": value_name(&args.kind),
+                "steps": steps,
+            })
+        );
+        return;
+    }
+    if args.css {
+        println!(":root {{");
+        for (label, clr) in labels.iter().zip(&colors) {
+            println!("  --{}-{}: #{};", args.name, label, clr.tohex(false));
+        }
+        println!("}}");
+        return;
+    }
+    for (label, clr) in labels.iter().zip(&colors) {
+        if args.preview {
+            print!("{}", clr.make_square());
+        }
+        println!(
+            "{}: {}",
+            label,
+            program_state.output_type.render_output(clr, false)
+        );
+    }
+}
+
+///The constraints that were given over the defaults, lightness is in percent like css oklch()
+fn random_constraints(
+    hue: Option<random::HueRange>,
+    min_lightness: Option<f64>,
+    max_lightness: Option<f64>,
+    min_chroma: Option<f64>,
+    max_chroma: Option<f64>,
+) -> random::Constraints {
+    let default = random::Constraints::default();
+    random::Constraints {
+        hue: hue.unwrap_or(default.hue),
+        lightness: (
+            min_lightness.map_or(default.lightness.0, |l| l / 100.0),
+            max_lightness.map_or(default.lightness.1, |l| l / 100.0),
+        ),
+        chroma: (
+            min_chroma.unwrap_or(default.chroma.0),
+            max_chroma.unwrap_or(default.chroma.1),
+        ),
+        contrast: None,
+    }
+}
+
+///A random color within the [random] constraints of the config that passes against the first
+///--cmp color, the alpha of the current color is kept
+fn randomize(program_state: &mut ProgramState) -> Result<(), String> {
+    let cfg = program_state.config.random.clone();
+    let cfg = cfg.as_ref();
+    let hue = match cfg.and_then(|cfg| cfg.hue.as_deref()) {
+        Some(hue) => Some(random::parse_hue_range(hue)?),
+        None => None,
+    };
+    let mut constraints = random_constraints(
+        hue,
+        cfg.and_then(|cfg| cfg.min_lightness),
+        cfg.and_then(|cfg| cfg.max_lightness),
+        cfg.and_then(|cfg| cfg.min_chroma),
+        cfg.and_then(|cfg| cfg.max_chroma),
+    );
+    if let Some(bg) = program_state.comparison_colors.first() {
+        let (r, g, b) = bg.rgb();
+        let algorithm = program_state.contrast_algorithm;
+        let min = cfg
+            .and_then(|cfg| cfg.min_contrast)
+            .unwrap_or(algorithm.default_target());
+        constraints.contrast = Some(([r, g, b], min, algorithm));
+    }
+    let [r, g, b] = constraints
+        .pick(&mut program_state.rng, None)
+        .ok_or_else(|| "No color matched the random constraints".to_owned())?;
+    program_state.curr_color.modify_rgb((r, g, b));
+    Ok(())
+}
+
+fn extract_action(args: &ExtractArgs, program_state: &ProgramState, json: bool) {
+    let image = match image::load(&args.image) {
+        Ok(image) => image,
+        Err(err) => exit_with_error(&err),
+    };
+    let swatches: Vec<(ColorRepresentation, f64)> =
+        match extract::extract(&image, args.count, &args.method) {
+        Ok(swatches) => swatches
+            .iter()
+            .map(|swatch| {
+                let [r, g, b] = swatch.rgb;
+                (ColorRepresentation::from_rgb(r, g, b), swatch.proportion)
+            })
+            .collect(),
+        Err(err) => exit_with_error(&err),
+    };
+    if json {
+        let colors: Vec<_> = swatches
+            .iter()
+            .map(|(clr, proportion)| {
+                serde_json::json!({
+                    "color": clr.to_json(),
+                    "proportion": proportion,
+                    "output": program_state.output_type.render_output(clr, false),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "image": args.image,
+                "width": image.width,
+                "height": image.height,
+                "method": value_name(&args.method),
+                "colors": colors,
+            })
+        );
+        return;
+    }
+    for (clr, proportion) in &swatches {
+        if args.preview {
+            print!("{}", clr.make_square());
+        }
+        println!(
+            "{} {:.1}%",
+            program_state.output_type.render_output(clr, false),
+            proportion * 100.0
+        );
+    }
+}
+
+fn random_action(args: &RandomArgs, program_state: &ProgramState, json: bool) {
+    let seed = args.seed.unwrap_or_else(random::seed_from_time);
+    let mut rng = random::Rng::new(seed);
+    let mut constraints = random_constraints(
+        args.hue,
+        args.min_lightness,
+        args.max_lightness,
+        args.min_chroma,
+        args.max_chroma,
+    );
+    let algorithm = args.algorithm.unwrap_or(ContrastAlgorithm::WCAG);
+    if let Some(bg) = &args.against {
+        let (r, g, b) = ColorRepresentation::from_color(bg, &program_state.clr_std).rgb();
+        let min = args.min_contrast.unwrap_or(algorithm.default_target());
+        constraints.contrast = Some(([r, g, b], min, algorithm));
+    }
+    let colors: Vec<ColorRepresentation> =
+        match constraints.generate(&mut rng, args.count, args.golden) {
+            Ok(colors) => colors
+                .iter()
+                .map(|clr| ColorRepresentation::from_rgb(clr[0], clr[1], clr[2]))
+                .collect(),
+            Err(err) => exit_with_error(&err),
+        };
+    if json {
+        let colors: Vec<_> = colors.iter().map(|clr| clr.to_json()).collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "seed": seed,
+                "colors": colors,
+            })
+        );
+        return;
+    }
+    for clr in &colors {
+        if args.preview {
+            print!("{}", clr.make_square());
+        }
+        println!("{}", program_state.output_type.render_output(clr, false));
+    }
+}
+
+fn adjust_action(args: &AdjustArgs, program_state: &ProgramState, json: bool) {
+    let adjustments = match adjust::parse(&args.adjustments, &program_state.clr_std) {
+        Ok(adjustments) => adjustments,
+        Err(err) => exit_with_error(&err),
+    };
+    let input = ColorRepresentation::from_color(&args.color, &program_state.clr_std);
+    let mut steps = vec![];
+    let mut color = input;
+    for adjustment in &adjustments {
+        color = adjustment.apply(&color);
+        steps.push((adjustment.describe(), color));
+    }
+    let enable_alpha = color.a != 255;
+    let output = program_state.output_type.render_output(&color, enable_alpha);
+    if json {
+        let steps: Vec<_> = steps
+            .iter()
+            .map(|(step, clr)| serde_json::json!({ "adjustment": step, "color": clr.to_json() }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "input": args.color,
+                "color": input.to_json(),
+                "steps": steps,
+                "result": color.to_json(),
+                "output": output,
+            })
+        );
+        return;
+    }
+    if args.preview {
+        println!("{}", color.make_square());
+    }
+    println!("{}", output);
+}
+
+fn blend_action(args: &BlendArgs, program_state: &ProgramState, json: bool) {
+    let rgba = |clr: &str| {
+        let clr = ColorRepresentation::from_color(clr, &program_state.clr_std);
+        [clr.r, clr.g, clr.b, clr.a as f64 / 255.0]
+    };
+    let [r, g, b, a] = blend(rgba(&args.top), rgba(&args.bottom), &args.mode);
+    let mut color = ColorRepresentation::from_rgb(r, g, b);
+    color.a = (a * 255.0).round() as u8;
+    //only show alpha when the result is still see-through
+    let enable_alpha = color.a != 255;
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "top": args.top,
+                "bottom": args.bottom,
+                "mode": value_name(&args.mode),
+                "color": color.to_json(),
+                "output": program_state.output_type.render_output(&color, enable_alpha),
+            })
+        );
+        return;
+    }
+    if args.preview {
+        println!("{}", color.make_square());
+    }
+    println!("{}", program_state.output_type.render_output(&color, enable_alpha));
+}
+
+fn gradient_action(args: &GradientArgs, program_state: &ProgramState, json: bool) {
+    if args.colors.len() < 2 {
+        exit_with_error("A gradient needs at least 2 colors");
+    }
+    let colors: Vec<[f64; 3]> = args
+        .colors
+        .iter()
+        .map(|clr| {
+            let (r, g, b) = ColorRepresentation::from_color(clr, &program_state.clr_std).rgb();
+            [r, g, b]
+        })
+        .collect();
+    let stops: Vec<(f64, ColorRepresentation)> = gradient(&colors, args.steps, &args.space, &args.hue)
+        .into_iter()
+        .enumerate()
+        .map(|(i, rgb)| {
+            let position = match args.steps {
+                1 => 0.0,
+                _ => i as f64 / (args.steps - 1) as f64,
+            };
+            (position, ColorRepresentation::from_rgb(rgb[0], rgb[1], rgb[2]))
+        })
+        .collect();
+    if json {
+        let stops: Vec<_> = stops
+            .iter()
+            .map(|(position, clr)| serde_json::json!({ "position": position, "color": clr.to_json() }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "space": value_name(&args.space),
+                "hue": value_name(&args.hue),
+                "stops": stops,
+            })
+        );
+        return;
+    }
+    if args.css {
+        let stops: Vec<String> = stops
+            .iter()
+            .map(|(position, clr)| format!("#{} {}%", clr.tohex(false), (position * 1000.0).round() / 10.0))
+            .collect();
+        //the browser interpolates between the stops too, so tell it to do it the same way
+        let mut method = format!("in {}", args.space.css_name());
+        if matches!(args.space, InterpolationSpace::OKLCH | InterpolationSpace::HSL) {
+            method += &format!(" {} hue", value_name(&args.hue));
+        }
+        println!("linear-gradient({}, {})", method, stops.join(", "));
+        return;
+    }
+    for (_, clr) in &stops {
+        if args.preview {
+            print!("{}", clr.make_square());
+        }
+        println!("{}", program_state.output_type.render_output(clr, false));
+    }
 }
 
 fn get_config_path() -> String {
@@ -731,19 +1903,139 @@ fn get_config_path() -> String {
     tpick_config_path + &String::from("/config.toml")
 }
 
+fn get_data_path() -> std::path::PathBuf {
+    let data_folder = match std::env::var("XDG_DATA_HOME") {
+        Ok(folder) if !folder.is_empty() => std::path::PathBuf::from(folder),
+        _ => std::path::PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".local/share"),
+    };
+    data_folder.join("tpick")
+}
+
 fn read_config_toml(config_path: &str) -> Config {
     let data = std::fs::read_to_string(config_path).unwrap_or("".to_string());
     toml::from_str(&data).unwrap()
 }
 
+///Resolves the [colors] and [palettes.<name>] sections of the config
+///palette colors are named <palette>.<color>, a color can be the name of another one, those are
+///resolved first and cycles are an error
+fn read_user_colors(config: &Config, clr_std: &ColorNameStandard) -> HashMap<String, [u8; 3]> {
+    let mut named: HashMap<String, (String, &String)> = HashMap::new();
+    if let Some(colors) = &config.colors {
+        for (name, clr) in colors {
+            named.insert(normalize_color_name(name), (name.to_owned(), clr));
+        }
+    }
+    if let Some(palettes) = &config.palettes {
+        for (palette, colors) in palettes {
+            for (name, clr) in colors {
+                let name = format!("{}.{}", palette, name);
+                named.insert(normalize_color_name(&name), (name, clr));
+            }
+        }
+    }
+    //sorted so that errors don't depend on the hashmap's order
+    let mut keys: Vec<&String> = named.keys().collect();
+    keys.sort();
+    let mut user_colors = HashMap::new();
+    for key in keys {
+        resolve_user_color(key, &named, clr_std, &mut user_colors, &mut vec![]);
+    }
+    user_colors
+}
+
+///Depth first, path is the chain of names currently being resolved
+fn resolve_user_color(
+    key: &str,
+    named: &HashMap<String, (String, &String)>,
+    clr_std: &ColorNameStandard,
+    resolved: &mut HashMap<String, [u8; 3]>,
+    path: &mut Vec<String>,
+) -> [u8; 3] {
+    if let Some(clr) = resolved.get(key) {
+        return *clr;
+    }
+    let (name, clr) = &named[key];
+    if let Some(start) = path.iter().position(|other| other == key) {
+        let mut cycle: Vec<&str> = path[start..].iter().map(|other| named[other].0.as_str()).collect();
+        cycle.push(name);
+        exit_with_error(&format!("Color cycle in config: {}", cycle.join(" -> ")));
+    }
+    let reference = normalize_color_name(clr);
+    let rgb = if named.contains_key(&reference) {
+        path.push(key.to_owned());
+        let rgb = resolve_user_color(&reference, named, clr_std, resolved, path);
+        path.pop();
+        rgb
+    } else {
+        match ColorRepresentation::try_from_color(clr, clr_std) {
+            Ok(clr) => [clr.r as u8, clr.g as u8, clr.b as u8],
+            Err(err) => exit_with_error(&format!("Invalid color for {} in config: {}", name, err)),
+        }
+    };
+    resolved.insert(key.to_owned(), rgb);
+    rgb
+}
+
+///Replaces the name of a config palette with the names of its colors, sorted by name
+///a :percent after the palette is kept on each of its colors
+fn expand_palettes(colors: &[String]) -> Vec<String> {
+    let Some(user_colors) = user_colors() else {
+        return colors.to_vec();
+    };
+    let mut expanded = vec![];
+    for clr in colors {
+        let (name, percent) = match clr.split_once(":") {
+            Some((name, percent)) => (name, format!(":{}", percent)),
+            None => (clr.as_str(), String::new()),
+        };
+        let prefix = format!("{}.", normalize_color_name(name));
+        let mut palette: Vec<&String> =
+            user_colors.keys().filter(|name| name.starts_with(&prefix)).collect();
+        if palette.is_empty() || user_colors.contains_key(&normalize_color_name(name)) {
+            expanded.push(clr.to_owned());
+        } else {
+            palette.sort();
+            expanded.extend(palette.into_iter().map(|name| format!("{}{}", name, percent)));
+        }
+    }
+    expanded
+}
+
 fn main() {
-    let mut reader = std::io::stdin();
+    let mut args = Args::parse();
 
-    let args = Args::parse();
+    let json = args.json;
 
     let mut starting_clr = args.color.unwrap_or("#ff0000".to_string());
     let clr_std = args.clr_standard.unwrap_or(ColorNameStandard::W3C);
 
+    if starting_clr == "-".to_string() {
+        starting_clr = String::new();
+        let _ = std::io::stdin().read_line(&mut starting_clr);
+        starting_clr = starting_clr.trim().to_string();
+    }
+
+    //colors have to be parsed after this, myterm color names query the terminal
+    let (tios_initial, _tios) = setup_term();
+
+    let config_path = get_config_path();
+    let cfg = read_config_toml(&config_path);
+    set_user_colors(read_user_colors(&cfg, &clr_std));
+    match &mut args.action {
+        Some(Actions::Contrast(contrast_args)) => {
+            contrast_args.colors = expand_palettes(&contrast_args.colors)
+        }
+        Some(Actions::Gradient(gradient_args)) => {
+            gradient_args.colors = expand_palettes(&gradient_args.colors)
+        }
+        Some(Actions::Mix(mix_args)) => mix_args.with = expand_palettes(&mix_args.with),
+        Some(Actions::Simulate(simulate_args)) => {
+            simulate_args.colors = expand_palettes(&simulate_args.colors)
+        }
+        _ => {}
+    }
+
     let requested_bg_color =
         ColorRepresentation::from_color(&args.bg_clr.unwrap_or("#000000".to_string()), &clr_std)
             .tohex(false);
@@ -754,12 +2046,6 @@ fn main() {
 
     let requested_input_type = args.input_type.unwrap_or(SelectionType::HSL);
 
-    if starting_clr == "-".to_string() {
-        starting_clr = String::new();
-        let _ = reader.read_line(&mut starting_clr);
-        starting_clr = starting_clr.trim().to_string();
-    }
-
     let output_type = match args.output_type.clone().unwrap_or(RequestedOutputType::HSL) {
         RequestedOutputType::HSL => OutputType::HSL,
         RequestedOutputType::RGB => OutputType::RGB,
@@ -773,10 +2059,10 @@ fn main() {
     } else {
         false
     };
-    let (tios_initial, _tios) = setup_term();
 
     if args.list_colors {
-        for (k, v) in clr_std.list_colors() {
+        let user_clrs = user_colors().cloned().unwrap_or_default();
+        for (k, v) in user_clrs.into_iter().chain(clr_std.list_colors().clone()) {
             println!(
                 "{}: {}",
                 k,
@@ -793,9 +2079,6 @@ fn main() {
         return;
     }
 
-    let config_path = get_config_path();
-    let cfg = read_config_toml(&config_path);
-
     let cycle_to_use = args.output_cycle.unwrap_or("default".to_owned());
     let cycle = OutputType::get_order_by_name(&cfg, &cycle_to_use);
 
@@ -808,15 +2091,19 @@ fn main() {
 
     let mut comparison_colors = vec![];
     if let Some(clrs) = args.compare {
-        for clr in clrs.split(" ") {
+        let clrs: Vec<String> = clrs.split(" ").map(|clr| clr.to_owned()).collect();
+        for clr in expand_palettes(&clrs) {
             comparison_colors.push(ColorRepresentation::from_color(&clr, &clr_std));
         }
     }
 
     let mut mix_colors = vec![];
     if let Some(clrs) = args.mix_colors {
-        for clr in clrs.split(" ") {
-            mix_colors.push(ColorRepresentation::from_color(&clr, &clr_std));
+        let clrs: Vec<String> = clrs.split(" ").map(|clr| clr.to_owned()).collect();
+        for clr in &expand_palettes(&clrs) {
+            let (clr_name, percent) =
+                parse_color_and_percent(clr).unwrap_or_else(|err| exit_with_error(&err));
+            mix_colors.push((ColorRepresentation::from_color(clr_name, &clr_std), percent));
         }
     }
 
@@ -833,48 +2120,138 @@ fn main() {
         cfg.to_owned(),
         comparison_colors,
         mix_colors,
+        args.mix_space,
     );
 
     if let Some(Actions::Convert(conversion)) = args.action {
-        convert_action(conversion, &program_state.curr_color);
+        convert_action(conversion, &starting_clr, &program_state.curr_color, json);
         close_term(&tios_initial);
         return;
     }
 
     if let Some(Actions::Invert(i_args)) = &args.action {
-        invert_action(&i_args, &program_state);
+        invert_action(&i_args, &program_state, json);
         close_term(&tios_initial);
         return;
     }
 
     if let Some(Actions::Mix(mixing)) = args.action {
-        mix_action(&mixing, &program_state);
+        mix_action(&mixing, &program_state, json);
         close_term(&tios_initial);
         return;
     };
 
+    if let Some(Actions::Extract(e_args)) = &args.action {
+        extract_action(&e_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Random(r_args)) = &args.action {
+        random_action(&r_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Adjust(a_args)) = &args.action {
+        adjust_action(&a_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Blend(b_args)) = &args.action {
+        blend_action(&b_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Gradient(g_args)) = &args.action {
+        gradient_action(&g_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Scale(s_args)) = &args.action {
+        scale_action(&s_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Harmony(h_args)) = &args.action {
+        harmony_action(&h_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Name(n_args)) = &args.action {
+        name_action(&n_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Diff(d_args)) = &args.action {
+        diff_action(&d_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
+    if let Some(Actions::Simulate(s_args)) = &args.action {
+        simulate_action(&s_args, &program_state, json);
+        close_term(&tios_initial);
+        return;
+    }
+
     if let Some(Actions::Contrast(args)) = args.action {
-        contrast_action(&args, &program_state);
+        let passed = contrast_action(&args, &program_state, json);
         close_term(&tios_initial);
+        if !passed {
+            unsafe { exit(1) };
+        }
         return;
     }
 
+    if let Some(algorithm) = args.contrast_algorithm {
+        program_state.contrast_algorithm = algorithm;
+    }
+
     let key_mappings = keymaps::init_keymaps(&program_state.config);
+    //report a bad backend now rather than on the first copy
+    if let Err(err) = program_state.clipboard() {
+        exit_with_error(&err);
+    }
+    program_state.status = program_state.registers.error().cloned();
 
     eprint!("\x1b[?1049h");
 
-    let bg_color = query_color(11, &mut reader);
-    let fg_color = query_color(10, &mut reader);
+    let bg_color = query_color(11);
+    let fg_color = query_color(10);
     if use_custom_colors {
-        eprint!("\x1b]11;#{}\x07", requested_bg_color);
-        eprint!("\x1b]10;#{}\x07", requested_fg_color);
+        send_osc(&format!("11;#{}", requested_bg_color));
+        send_osc(&format!("10;#{}", requested_fg_color));
+    }
+    let shown_bg = if use_custom_colors {
+        Some(format!("#{}", requested_bg_color))
+    } else {
+        bg_color.clone()
+    };
+    if let Some(bg) = shown_bg {
+        let (r, g, b) = hex62rgb(&bg[1..]);
+        program_state.terminal_bg = [r as f64, g as f64, b as f64];
     }
     eprint!("\x1b[?25l");
 
     cls();
 
+    watch_window_area();
+
+    let mut last_frame = None;
+
     loop {
-        let [_rows, cols] = query_window_area(&mut reader);
+        if WINDOW_RESIZED.swap(false, Ordering::SeqCst) {
+            update_window_area();
+            cls();
+        }
+        let [rows, cols] = window_area();
 
         //this variable keeps track of the step for the step increase for the HSL/RGB rendering
         let step = (360.0
@@ -884,9 +2261,15 @@ fn main() {
         let square_count = (360.0 / step).ceil() as u32;
         render_display(&program_state, square_count, step);
         //after it finishes rendering, there should be nothing below it
-        eprint!("\x1b[J");
+        draw!("\x1b[J");
+        if let Some(msg) = program_state.status.take() {
+            draw!("\n\x1b[31m{}\x1b[0m", msg);
+        }
+        screen::present(&mut last_frame, rows as usize, cols as usize);
 
-        let data = get_input(&mut reader);
+        let Some(data) = get_input() else {
+            break;
+        };
 
         if let Some(f) = key_mappings.get(&data) {
             if let Some(action) = f(&mut program_state, &data) {
@@ -901,8 +2284,15 @@ fn main() {
 
     eprint!("\x1b[?1049l");
     eprint!("\x1b[?25h");
-    eprint!("\x1b]11;{}\x07", bg_color);
-    eprint!("\x1b]10;{}\x07", fg_color);
+    //111 and 110 reset to the default colors
+    match bg_color {
+        Some(clr) => send_osc(&format!("11;{}", clr)),
+        None => send_osc("111"),
+    }
+    match fg_color {
+        Some(clr) => send_osc(&format!("10;{}", clr)),
+        None => send_osc("110"),
+    }
 
     if args.print_on_exit {
         cls();
@@ -0,0 +1,451 @@
+use crate::image::{pixel_count, Image};
+
+pub const SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+///Reads the bits of a deflate stream, least significant bit first
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buf: u32,
+    count: u32,
+}
+
+impl BitReader<'_> {
+    fn bits(&mut self, n: u32) -> Result<u32, String> {
+        while self.count < n {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| "Unexpected end of compressed data".to_owned())?;
+            self.pos += 1;
+            self.buf |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+        let value = self.buf & ((1u64 << n) - 1) as u32;
+        self.buf >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        self.buf = 0;
+        self.count = 0;
+    }
+}
+
+///A canonical huffman code, stored as the number of codes of each length and the symbols
+///ordered by code
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for len in lengths {
+            counts[*len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                symbols[offsets[*len as usize] as usize] = symbol as u16;
+                offsets[*len as usize] += 1;
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String> {
+        //codes are read one bit at a time, most significant bit first
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("Invalid huffman code".to_owned())
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+///The order the code length code lengths are stored in
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    (Huffman::new(&lengths), Huffman::new(&[5; 30]))
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let lit_count = reader.bits(5)? as usize + 257;
+    let dist_count = reader.bits(5)? as usize + 1;
+    let code_count = reader.bits(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for i in CODE_LENGTH_ORDER.iter().take(code_count) {
+        code_lengths[*i] = reader.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+    let mut lengths = vec![];
+    while lengths.len() < lit_count + dist_count {
+        let symbol = code_lengths.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let prev = *lengths
+                    .last()
+                    .ok_or_else(|| "Repeated a length before the first one".to_owned())?;
+                (prev, 3 + reader.bits(2)?)
+            }
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+    if lengths.len() > lit_count + dist_count {
+        return Err("Too many code lengths".to_owned());
+    }
+    Ok((
+        Huffman::new(&lengths[..lit_count]),
+        Huffman::new(&lengths[lit_count..]),
+    ))
+}
+
+///Decompresses a zlib stream, the checksum is not verified
+///fails as soon as the output would grow past limit bytes, so small bombs can't use up the memory
+pub fn inflate(data: &[u8], limit: usize) -> Result<Vec<u8>, String> {
+    if data.len() < 2 || data[0] & 0x0f != 8 || data[1] & 0x20 != 0 {
+        return Err("Unsupported zlib stream".to_owned());
+    }
+    let mut reader = BitReader {
+        data: &data[2..],
+        pos: 0,
+        buf: 0,
+        count: 0,
+    };
+    let too_big = || "More compressed data than the image needs".to_owned();
+    let mut out: Vec<u8> = vec![];
+    loop {
+        let last = reader.bits(1)? == 1;
+        let (lit, dist) = match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let start = reader.pos;
+                let truncated = || "Unexpected end of compressed data".to_owned();
+                let header = reader.data.get(start..start + 4).ok_or_else(truncated)?;
+                let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+                let block = reader
+                    .data
+                    .get(start + 4..start + 4 + len)
+                    .ok_or_else(truncated)?;
+                if out.len() + len > limit {
+                    return Err(too_big());
+                }
+                out.extend_from_slice(block);
+                reader.pos += 4 + len;
+                if last {
+                    break;
+                }
+                continue;
+            }
+            1 => fixed_tables(),
+            2 => dynamic_tables(&mut reader)?,
+            _ => return Err("Invalid block type".to_owned()),
+        };
+        loop {
+            let symbol = lit.decode(&mut reader)? as usize;
+            match symbol {
+                0..=255 if out.len() >= limit => return Err(too_big()),
+                0..=255 => out.push(symbol as u8),
+                256 => break,
+                _ => {
+                    let idx = symbol - 257;
+                    if idx >= LENGTH_BASE.len() {
+                        return Err("Invalid length".to_owned());
+                    }
+                    let len =
+                        LENGTH_BASE[idx] as usize + reader.bits(LENGTH_EXTRA[idx] as u32)? as usize;
+                    let idx = dist.decode(&mut reader)? as usize;
+                    if idx >= DIST_BASE.len() {
+                        return Err("Invalid distance".to_owned());
+                    }
+                    let distance =
+                        DIST_BASE[idx] as usize + reader.bits(DIST_EXTRA[idx] as u32)? as usize;
+                    if distance > out.len() {
+                        return Err("Distance is too far back".to_owned());
+                    }
+                    if out.len() + len > limit {
+                        return Err(too_big());
+                    }
+                    //the copy may overlap what it is writing
+                    let start = out.len() - distance;
+                    for i in 0..len {
+                        out.push(out[start + i]);
+                    }
+                }
+            }
+        }
+        if last {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+struct Header {
+    width: usize,
+    height: usize,
+    depth: u8,
+    color_type: u8,
+    interlaced: bool,
+}
+
+impl Header {
+    fn channels(&self) -> usize {
+        match self.color_type {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        }
+    }
+
+    fn bits_per_pixel(&self) -> usize {
+        self.channels() * self.depth as usize
+    }
+
+    fn stride(&self, width: usize) -> usize {
+        (width * self.bits_per_pixel()).div_ceil(8)
+    }
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+///Undoes the per row filters of a (sub)image, returns the rows without their filter bytes
+fn unfilter(data: &[u8], header: &Header, width: usize, height: usize) -> Result<Vec<u8>, String> {
+    let stride = header.stride(width);
+    let bpp = header.bits_per_pixel().div_ceil(8);
+    if data.len() < (stride + 1) * height {
+        return Err("Not enough image data".to_owned());
+    }
+    let mut out = vec![0u8; stride * height];
+    for y in 0..height {
+        let filter = data[y * (stride + 1)];
+        let row = &data[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+        for x in 0..stride {
+            let a = if x >= bpp {
+                out[y * stride + x - bpp]
+            } else {
+                0
+            };
+            let b = if y > 0 { out[(y - 1) * stride + x] } else { 0 };
+            let c = if x >= bpp && y > 0 {
+                out[(y - 1) * stride + x - bpp]
+            } else {
+                0
+            };
+            out[y * stride + x] = row[x].wrapping_add(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(format!("Invalid filter type: {}", filter)),
+            });
+        }
+    }
+    Ok(out)
+}
+
+///The rgba pixel at x of an unfiltered row
+fn pixel(row: &[u8], x: usize, header: &Header, palette: &[[u8; 4]]) -> [u8; 4] {
+    let depth = header.depth as usize;
+    //16 bit samples keep their high byte
+    let raw = |i: usize| -> usize {
+        match depth {
+            8 => row[i] as usize,
+            16 => row[i * 2] as usize,
+            _ => {
+                let bit = i * depth;
+                ((row[bit / 8] >> (8 - depth - bit % 8)) & ((1 << depth) - 1)) as usize
+            }
+        }
+    };
+    //samples are scaled to 8 bits
+    let sample = |i: usize| -> u8 {
+        match depth {
+            1 | 2 | 4 => (raw(i) * 255 / ((1 << depth) - 1)) as u8,
+            _ => raw(i) as u8,
+        }
+    };
+    let i = x * header.channels();
+    match header.color_type {
+        0 => {
+            let v = sample(i);
+            [v, v, v, 255]
+        }
+        2 => [sample(i), sample(i + 1), sample(i + 2), 255],
+        3 => palette.get(raw(x)).copied().unwrap_or([0, 0, 0, 255]),
+        4 => {
+            let v = sample(i);
+            [v, v, v, sample(i + 1)]
+        }
+        _ => [sample(i), sample(i + 1), sample(i + 2), sample(i + 3)],
+    }
+}
+
+pub fn decode(data: &[u8]) -> Result<Image, String> {
+    let mut pos = SIGNATURE.len();
+    let mut header = None;
+    let mut palette: Vec<[u8; 4]> = vec![];
+    let mut compressed = vec![];
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or_else(|| "Unexpected end of file".to_owned())?;
+        match kind {
+            b"IHDR" if len >= 13 => {
+                header = Some(Header {
+                    width: u32::from_be_bytes(body[0..4].try_into().unwrap()) as usize,
+                    height: u32::from_be_bytes(body[4..8].try_into().unwrap()) as usize,
+                    depth: body[8],
+                    color_type: body[9],
+                    interlaced: body[12] == 1,
+                });
+            }
+            b"PLTE" => {
+                if !len.is_multiple_of(3) || len > 256 * 3 {
+                    return Err("Invalid palette".to_owned());
+                }
+                palette = body
+                    .chunks_exact(3)
+                    .map(|c| [c[0], c[1], c[2], 255])
+                    .collect();
+            }
+            b"tRNS" => {
+                for (entry, alpha) in palette.iter_mut().zip(body) {
+                    entry[3] = *alpha;
+                }
+            }
+            b"IDAT" => compressed.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        //the crc is not checked
+        pos += 12 + len;
+    }
+    let header = header.ok_or_else(|| "Missing IHDR chunk".to_owned())?;
+    if compressed.is_empty() {
+        return Err("Missing IDAT chunk".to_owned());
+    }
+    let valid_depth = match header.color_type {
+        0 => [1, 2, 4, 8, 16].contains(&header.depth),
+        3 => [1, 2, 4, 8].contains(&header.depth),
+        2 | 4 | 6 => [8, 16].contains(&header.depth),
+        _ => false,
+    };
+    if !valid_depth {
+        return Err(format!(
+            "Unsupported color type {} with bit depth {}",
+            header.color_type, header.depth
+        ));
+    }
+    let (width, height) = (header.width, header.height);
+    let count = pixel_count(width, height)?;
+    //(x start, y start, x step, y step) of each Adam7 pass
+    let passes: &[(usize, usize, usize, usize)] = if header.interlaced {
+        &[
+            (0, 0, 8, 8),
+            (4, 0, 8, 8),
+            (0, 4, 4, 8),
+            (2, 0, 4, 4),
+            (0, 2, 2, 4),
+            (1, 0, 2, 2),
+            (0, 1, 1, 2),
+        ]
+    } else {
+        &[(0, 0, 1, 1)]
+    };
+    //(x start, y start, x step, y step, width, height) of the passes that have any pixels
+    let passes: Vec<_> = passes
+        .iter()
+        .map(|(x0, y0, dx, dy)| {
+            let pass_width = (width + dx - 1 - x0) / dx;
+            let pass_height = (height + dy - 1 - y0) / dy;
+            (*x0, *y0, *dx, *dy, pass_width, pass_height)
+        })
+        .filter(|pass| pass.4 > 0 && pass.5 > 0)
+        .collect();
+    let size: usize = passes
+        .iter()
+        .map(|pass| (header.stride(pass.4) + 1) * pass.5)
+        .sum();
+    let raw = inflate(&compressed, size)?;
+    if raw.len() < size {
+        return Err("Not enough image data".to_owned());
+    }
+    let mut pixels = vec![[0, 0, 0, 0]; count];
+    let mut offset = 0;
+    for (x0, y0, dx, dy, pass_width, pass_height) in passes {
+        let rows = unfilter(&raw[offset..], &header, pass_width, pass_height)?;
+        offset += (header.stride(pass_width) + 1) * pass_height;
+        let stride = header.stride(pass_width);
+        for y in 0..pass_height {
+            let row = &rows[y * stride..(y + 1) * stride];
+            for x in 0..pass_width {
+                pixels[(y0 + y * dy) * width + x0 + x * dx] = pixel(row, x, &header, &palette);
+            }
+        }
+    }
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
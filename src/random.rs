@@ -0,0 +1,149 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::color_conversions::{oklch2rgb, oklch_in_gamut, ContrastAlgorithm};
+
+///How many colors are tried before giving up on the constraints
+const ATTEMPTS: usize = 10000;
+///Stepping by this fraction of the hue range keeps every new hue far from the previous ones
+const GOLDEN_RATIO_CONJUGATE: f64 = 0.618033988749895;
+
+///splitmix64, small and good enough for picking colors, the same seed always gives the same
+///colors on every platform
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    ///From 0 up to but not including 1
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+}
+
+pub fn seed_from_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+///A range of hues in degrees going clockwise from start to end, so 330-30 is the reds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HueRange {
+    start: f64,
+    end: f64,
+}
+
+impl HueRange {
+    pub const ALL: HueRange = HueRange {
+        start: 0.0,
+        end: 360.0,
+    };
+
+    fn width(&self) -> f64 {
+        if self.end > self.start {
+            self.end - self.start
+        } else {
+            self.end + 360.0 - self.start
+        }
+    }
+
+    ///The hue t (0-1) of the way through the range
+    fn at(&self, t: f64) -> f64 {
+        (self.start + self.width() * t).rem_euclid(360.0)
+    }
+}
+
+///Parses a hue range such as 200-260
+pub fn parse_hue_range(range: &str) -> Result<HueRange, String> {
+    let invalid = || format!("Invalid hue range: {}, expected start-end such as 200-260", range);
+    let (start, end) = range.split_once("-").ok_or_else(invalid)?;
+    let start: f64 = start.trim().parse().map_err(|_| invalid())?;
+    let end: f64 = end.trim().parse().map_err(|_| invalid())?;
+    Ok(HueRange {
+        start: start.rem_euclid(360.0),
+        end: if end == 360.0 { end } else { end.rem_euclid(360.0) },
+    })
+}
+
+///What the random colors have to be, lightness and chroma are in OKLCH
+pub struct Constraints {
+    pub hue: HueRange,
+    ///From 0-1
+    pub lightness: (f64, f64),
+    pub chroma: (f64, f64),
+    ///The background to contrast against and the minimum contrast
+    pub contrast: Option<([f64; 3], f64, ContrastAlgorithm)>,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Constraints {
+            hue: HueRange::ALL,
+            lightness: (0.0, 1.0),
+            //a little over the most saturated sRGB colors
+            chroma: (0.0, 0.37),
+            contrast: None,
+        }
+    }
+}
+
+impl Constraints {
+    fn accepts(&self, clr: [f64; 3]) -> bool {
+        match &self.contrast {
+            Some((bg, min, algorithm)) => algorithm.passes(algorithm.contrast(clr, *bg), *min),
+            None => true,
+        }
+    }
+
+    ///A random color with the hue t (0-1) of the way through the hue range, or anywhere in the
+    ///range if hue_t is None
+    pub fn pick(&self, rng: &mut Rng, hue_t: Option<f64>) -> Option<[f64; 3]> {
+        for _ in 0..ATTEMPTS {
+            let h = self.hue.at(hue_t.unwrap_or_else(|| rng.next_f64()));
+            let l = rng.range(self.lightness.0, self.lightness.1);
+            let c = rng.range(self.chroma.0, self.chroma.1);
+            if !oklch_in_gamut(l, c, h) {
+                continue;
+            }
+            //check the color that gets printed, not the unrounded one
+            let (r, g, b) = oklch2rgb(l, c, h);
+            let clr = [r, g, b].map(f64::round);
+            if self.accepts(clr) {
+                return Some(clr);
+            }
+        }
+        None
+    }
+
+    ///count random colors, golden spreads the hues out so that the colors are easy to tell apart
+    pub fn generate(
+        &self,
+        rng: &mut Rng,
+        count: usize,
+        golden: bool,
+    ) -> Result<Vec<[f64; 3]>, String> {
+        let offset = rng.next_f64();
+        (0..count)
+            .map(|i| {
+                let hue_t = golden.then(|| (offset + i as f64 * GOLDEN_RATIO_CONJUGATE).fract());
+                self.pick(rng, hue_t)
+                    .ok_or_else(|| "No color matched the constraints, try loosening them".to_owned())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+///vim style registers (a-z) that copies can go to instead of the clipboard
+///they are saved in the data dir so that they last between sessions
+pub struct Registers {
+    path: PathBuf,
+    values: HashMap<String, String>,
+    ///Why the file could not be loaded, the file is never written over while this is set
+    error: Option<String>,
+}
+
+impl Registers {
+    pub fn is_named(name: char) -> bool {
+        name.is_ascii_lowercase()
+    }
+
+    pub fn load(path: PathBuf) -> Registers {
+        let loaded = match std::fs::read_to_string(&path) {
+            //toml's own message spans several lines, too many for the status line
+            Ok(data) => toml::from_str(&data).map_err(|err| {
+                let line = err.span().map_or(0, |span| data[..span.start].lines().count());
+                format!("line {}: {}", line, err.message().replace('\n', ", "))
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(err) => Err(err.to_string()),
+        };
+        let (values, error) = match loaded {
+            Ok(values) => (values, None),
+            Err(err) => (
+                HashMap::new(),
+                Some(format!("Could not load registers from {}: {}", path.display(), err)),
+            ),
+        };
+        Registers {
+            path,
+            values,
+            error,
+        }
+    }
+
+    pub fn error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+
+    pub fn get(&self, name: char) -> Result<&String, String> {
+        if let Some(err) = &self.error {
+            return Err(err.to_owned());
+        }
+        self.values
+            .get(&name.to_string())
+            .ok_or(format!("Register {} is empty", name))
+    }
+
+    pub fn set(&mut self, name: char, value: &str) -> Result<(), String> {
+        if let Some(err) = &self.error {
+            return Err(format!("{}, not saving over it", err));
+        }
+        self.values.insert(name.to_string(), value.to_owned());
+        let data = toml::to_string(&self.values).map_err(|err| err.to_string())?;
+        if let Some(dir) = self.path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        std::fs::write(&self.path, data)
+            .map_err(|err| format!("Could not save registers to {}: {}", self.path.display(), err))
+    }
+}
@@ -0,0 +1,311 @@
+use std::cell::RefCell;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+thread_local! {
+    ///Everything drawn with `draw!` ends up here until the frame is presented
+    static FRAME: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+///Set when something writes to the terminal outside of a frame (cls, prompts), the next frame
+///is then drawn in full instead of diffed
+static INVALIDATED: AtomicBool = AtomicBool::new(true);
+
+#[macro_export]
+macro_rules! draw {
+    ($($arg:tt)*) => {
+        $crate::screen::push_to_frame(&format!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! drawln {
+    () => {
+        $crate::screen::push_to_frame("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::screen::push_to_frame(&format!("{}\n", format!($($arg)*)))
+    };
+}
+
+pub fn push_to_frame(data: &str) {
+    FRAME.with(|frame| frame.borrow_mut().push_str(data));
+}
+
+pub fn invalidate() {
+    INVALIDATED.store(true, Ordering::SeqCst);
+}
+
+#[derive(Clone, Default, PartialEq, Debug)]
+struct Style {
+    attributes: Vec<u16>,
+    fg: Option<String>,
+    bg: Option<String>,
+}
+
+impl Style {
+    ///Applies the parameters of an SGR (`CSI ... m`) sequence
+    fn apply(&mut self, params: &str) {
+        let params: Vec<&str> = params.split(";").collect();
+        let mut i = 0;
+        while i < params.len() {
+            let n: u16 = params[i].parse().unwrap_or(0);
+            match n {
+                0 => *self = Style::default(),
+                38 | 48 => {
+                    //38;5;n and 38;2;r;g;b
+                    let len = match params.get(i + 1) {
+                        Some(&"5") => 3,
+                        Some(&"2") => 5,
+                        _ => 1,
+                    };
+                    let end = params.len().min(i + len);
+                    let clr = Some(params[i..end].join(";"));
+                    if n == 38 {
+                        self.fg = clr;
+                    } else {
+                        self.bg = clr;
+                    }
+                    i = end;
+                    continue;
+                }
+                30..=37 | 90..=97 => self.fg = Some(n.to_string()),
+                39 => self.fg = None,
+                40..=47 | 100..=107 => self.bg = Some(n.to_string()),
+                49 => self.bg = None,
+                _ => {
+                    if !self.attributes.contains(&n) {
+                        self.attributes.push(n);
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    fn sgr(&self) -> String {
+        let mut params = vec!["0".to_owned()];
+        params.extend(self.attributes.iter().map(|n| n.to_string()));
+        params.extend(self.fg.iter().cloned());
+        params.extend(self.bg.iter().cloned());
+        format!("\x1b[{}m", params.join(";"))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Cell {
+    ch: char,
+    style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            ch: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+///A model of the terminal screen, it understands the subset of escape sequences that the
+///renderers use so that two frames can be compared cell by cell
+#[derive(Debug)]
+pub struct Screen {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Cell>,
+    row: usize,
+    col: usize,
+    saved: (usize, usize),
+    style: Style,
+    //the cursor is past the last column, the next character goes on the next line
+    wrap_pending: bool,
+}
+
+impl Screen {
+    pub fn new(rows: usize, cols: usize) -> Screen {
+        Screen {
+            rows,
+            cols,
+            cells: vec![Cell::default(); rows * cols],
+            row: 0,
+            col: 0,
+            saved: (0, 0),
+            style: Style::default(),
+            wrap_pending: false,
+        }
+    }
+
+    fn put(&mut self, ch: char) {
+        if self.wrap_pending {
+            self.row += 1;
+            self.col = 0;
+            self.wrap_pending = false;
+        }
+        //anything below the screen is dropped instead of scrolling
+        if self.row < self.rows {
+            self.cells[self.row * self.cols + self.col] = Cell {
+                ch,
+                style: self.style.clone(),
+            };
+        }
+        if self.col + 1 == self.cols {
+            self.wrap_pending = true;
+        } else {
+            self.col += 1;
+        }
+    }
+
+    ///Erases the cells between the two (row, col) positions, inclusive
+    fn erase(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let start = from.0 * self.cols + from.1;
+        let end = ((to.0 * self.cols + to.1) + 1).min(self.cells.len());
+        for cell in self.cells.iter_mut().take(end).skip(start) {
+            *cell = Cell::default();
+        }
+    }
+
+    fn csi(&mut self, params: &str, cmd: char) {
+        //private modes such as ?25l don't affect the cells
+        if params.starts_with("?") {
+            return;
+        }
+        let nums: Vec<usize> = params.split(";").map(|n| n.parse().unwrap_or(0)).collect();
+        let n = nums[0].max(1);
+        let last_col = self.cols - 1;
+        match cmd {
+            'm' => {
+                self.style.apply(params);
+                return;
+            }
+            'H' | 'f' => {
+                self.row = nums[0].max(1) - 1;
+                self.col = (nums.get(1).copied().unwrap_or(1).max(1) - 1).min(last_col);
+            }
+            'A' => self.row = self.row.saturating_sub(n),
+            'B' => self.row += n,
+            'C' => self.col = (self.col + n).min(last_col),
+            'D' => self.col = self.col.saturating_sub(n),
+            'G' => self.col = (n - 1).min(last_col),
+            's' => self.saved = (self.row, self.col),
+            'u' => (self.row, self.col) = self.saved,
+            'K' if self.row < self.rows => match nums[0] {
+                1 => self.erase((self.row, 0), (self.row, self.col)),
+                2 => self.erase((self.row, 0), (self.row, last_col)),
+                _ => self.erase((self.row, self.col), (self.row, last_col)),
+            },
+            'J' => match nums[0] {
+                1 => self.erase((0, 0), (self.row, self.col)),
+                2 => self.erase((0, 0), (self.rows, 0)),
+                _ => self.erase((self.row, self.col), (self.rows, 0)),
+            },
+            _ => {}
+        }
+        self.wrap_pending = false;
+    }
+
+    pub fn write(&mut self, data: &str) {
+        if self.rows == 0 || self.cols == 0 {
+            return;
+        }
+        let mut chars = data.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\x1b' => match chars.next() {
+                    Some('[') => {
+                        let mut params = String::new();
+                        for c in chars.by_ref() {
+                            if ('\x40'..='\x7e').contains(&c) {
+                                self.csi(&params, c);
+                                break;
+                            }
+                            params.push(c);
+                        }
+                    }
+                    //OSC, ends with BEL or ST
+                    Some(']') => {
+                        while let Some(c) = chars.next() {
+                            if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                                break;
+                            }
+                        }
+                    }
+                    Some('7') => self.saved = (self.row, self.col),
+                    Some('8') => (self.row, self.col) = self.saved,
+                    _ => {}
+                },
+                //the terminal translates \n into \r\n
+                '\n' => {
+                    self.row += 1;
+                    self.col = 0;
+                    self.wrap_pending = false;
+                }
+                '\r' => {
+                    self.col = 0;
+                    self.wrap_pending = false;
+                }
+                c if c.is_control() => {}
+                c => self.put(c),
+            }
+        }
+    }
+
+    ///The output needed to turn `prev` into this screen, or to draw the whole screen if there
+    ///is no previous frame
+    pub fn diff(&self, prev: Option<&Screen>) -> String {
+        let mut out = String::new();
+        if prev.is_none() {
+            out += "\x1b[0m\x1b[2J";
+        }
+        let blank = Cell::default();
+        let mut cursor = None;
+        let mut style = None;
+        for (i, cell) in self.cells.iter().enumerate() {
+            let old = prev.map(|p| &p.cells[i]).unwrap_or(&blank);
+            if old == cell {
+                continue;
+            }
+            let (row, col) = (i / self.cols, i % self.cols);
+            if cursor != Some((row, col)) {
+                out += &format!("\x1b[{};{}H", row + 1, col + 1);
+            }
+            if style != Some(&cell.style) {
+                out += &cell.style.sgr();
+                style = Some(&cell.style);
+            }
+            out.push(cell.ch);
+            //avoid relying on where the terminal leaves the cursor after the last column
+            cursor = if col + 1 < self.cols {
+                Some((row, col + 1))
+            } else {
+                None
+            };
+        }
+        if style.is_some() {
+            out += "\x1b[0m";
+        }
+        out
+    }
+}
+
+///Draws everything that was drawn with `draw!` since the last call, only the cells that changed
+///since `last_frame` are written, in a single synchronized update
+pub fn present(last_frame: &mut Option<Screen>, rows: usize, cols: usize) {
+    let data = FRAME.with(|frame| std::mem::take(&mut *frame.borrow_mut()));
+    let mut screen = Screen::new(rows, cols);
+    screen.write(&data);
+
+    let invalidated = INVALIDATED.swap(false, Ordering::SeqCst);
+    let prev = match last_frame {
+        Some(prev) if !invalidated && prev.rows == rows && prev.cols == cols => Some(&*prev),
+        _ => None,
+    };
+    let changes = screen.diff(prev);
+
+    //terminals that don't know about mode 2026 ignore it
+    let mut stderr = std::io::stderr().lock();
+    let _ = write!(stderr, "\x1b[?2026h{}\x1b[?2026l", changes);
+    let _ = stderr.flush();
+
+    *last_frame = Some(screen);
+}
@@ -1,4 +1,14 @@
-use crate::{contrast, hsl2rgb, rgb2hsl};
+use crate::extract::{extract, ExtractMethod};
+use crate::image::Image;
+use crate::{jpeg, png};
+use crate::random::{parse_hue_range, Constraints, Rng};
+use crate::screen::Screen;
+use crate::ColorRepresentation;
+use crate::{
+    apca_contrast, blend, cie76, cie94, ciede2000, color_mix, contrast, fix_contrast, hsl2rgb,
+    name_to_hex, rgb2hsl, rgb2oklch, scale, simulate_cvd, BlendMode, ColorNameStandard,
+    ContrastAlgorithm, Deficiency, DeltaE, HueInterpolation, LightnessSpace, MixSpace, ScaleKind,
+};
 
 #[test]
 fn color_conversions() {
@@ -6,3 +16,471 @@ fn color_conversions() {
     assert_eq!(hsl2rgb(331.7647, 100.0, 50.0), (255.0, 0.0, 120.0));
     assert_eq!(contrast([255.0, 255.0, 255.0], [0.0, 0.0, 255.0]), 8.592471);
 }
+
+#[test]
+fn apca() {
+    let grey = [136.0, 136.0, 136.0];
+    let white = [255.0, 255.0, 255.0];
+    assert_eq!((apca_contrast(grey, white) * 10.0).round(), 631.0);
+    assert_eq!((apca_contrast(white, grey) * 10.0).round(), -685.0);
+}
+
+#[test]
+fn contrast_fix() {
+    let white = [255.0, 255.0, 255.0];
+    let grey = [0x77 as f64; 3];
+    for space in [LightnessSpace::HSL, LightnessSpace::OKLCH] {
+        //#777777 is just short of 4.5 against white, #767676 is the closest that passes
+        let fixed = fix_contrast(grey, white, 4.5, &ContrastAlgorithm::WCAG, &space);
+        assert_eq!(fixed, Some([0x76 as f64; 3]));
+        let red = [255.0, 0.0, 0.0];
+        let fixed = fix_contrast(red, white, 4.5, &ContrastAlgorithm::WCAG, &space).unwrap();
+        assert!(contrast(fixed, white) >= 4.5);
+        assert_eq!(fixed, fixed.map(f64::round));
+        assert!(fix_contrast(grey, white, 22.0, &ContrastAlgorithm::WCAG, &space).is_none());
+        assert!(fix_contrast(grey, grey, 60.0, &ContrastAlgorithm::APCA, &space).is_some());
+        assert!(fix_contrast(grey, white, 120.0, &ContrastAlgorithm::APCA, &space).is_none());
+    }
+}
+
+#[test]
+fn contrast_table() {
+    let colors = ["red", "white", "black"]
+        .map(|name| ColorRepresentation::from_color(name, &ColorNameStandard::W3C));
+    let matrix = crate::contrast_matrix(&colors, &ContrastAlgorithm::WCAG);
+    let rounded: Vec<Vec<f64>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|v| (v * 100.0).round() / 100.0).collect())
+        .collect();
+    assert_eq!(rounded, [[1.0, 4.0, 5.25], [4.0, 1.0, 21.0], [5.25, 21.0, 1.0]]);
+    let matrix = crate::contrast_matrix(&colors, &ContrastAlgorithm::APCA);
+    //apca depends on which color is the text
+    assert_ne!(matrix[0][1], matrix[1][0]);
+    assert_eq!(matrix[1][1], 0.0);
+
+    assert_eq!(crate::csv_field("#ff0000"), "#ff0000");
+    assert_eq!(crate::csv_field("rgb(255,0,0)"), "\"rgb(255,0,0)\"");
+    assert_eq!(crate::csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(crate::csv_field("two\nlines"), "\"two\nlines\"");
+}
+
+#[test]
+fn delta_e() {
+    //pairs from Sharma, Wu and Dalal's CIEDE2000 test data
+    let de = ciede2000((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485));
+    assert_eq!((de * 10000.0).round(), 20425.0);
+    let de = ciede2000((50.0, 2.5, 0.0), (73.0, 25.0, -18.0));
+    assert_eq!((de * 10000.0).round(), 271492.0);
+    //the same pairs with the older formulas, cie94 uses the graphic arts weights
+    let de = cie76((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485));
+    assert_eq!((de * 10000.0).round(), 40011.0);
+    let de = cie94((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485));
+    assert_eq!((de * 10000.0).round(), 13950.0);
+    let de = cie94((50.0, 2.5, 0.0), (73.0, 25.0, -18.0));
+    assert_eq!((de * 10000.0).round(), 346892.0);
+    //oklab's red is (0.627955, 0.224863, 0.125846) and its blue (0.452014, -0.032457, -0.311528)
+    let (red, blue) = ([255.0, 0.0, 0.0], [0.0, 0.0, 255.0]);
+    assert_eq!((crate::delta_e(red, blue, &DeltaE::OKLab) * 10000.0).round(), 5371.0);
+    let (white, black) = ([255.0; 3], [0.0; 3]);
+    assert_eq!((crate::delta_e(white, black, &DeltaE::OKLab) * 10000.0).round(), 10000.0);
+    assert_eq!((crate::delta_e(white, black, &DeltaE::CIE76) * 100.0).round(), 10000.0);
+}
+
+#[test]
+fn nearest_names() {
+    let xterm_red = [205.0, 0.0, 0.0];
+    let names = |standard: ColorNameStandard, count: usize| -> Vec<(String, f64)> {
+        standard
+            .nearest_colors(xterm_red, count, &DeltaE::CIEDE2000)
+            .into_iter()
+            .map(|(name, _, de)| (name, (de * 100.0).round() / 100.0))
+            .collect()
+    };
+    let w3c = names(ColorNameStandard::W3C, 4);
+    assert_eq!(
+        w3c.iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        ["firebrick", "brown", "red", "crimson"]
+    );
+    assert!(w3c.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    //the exact match comes first in the standards that have it
+    assert_eq!(
+        names(ColorNameStandard::X11, 2),
+        [("red3".to_owned(), 0.0), ("firebrick3".to_owned(), 5.38)]
+    );
+    assert_eq!(
+        names(ColorNameStandard::XTerm, 1),
+        [("red".to_owned(), 0.0)]
+    );
+    assert_eq!(names(ColorNameStandard::W3C, 1), w3c[..1]);
+}
+
+#[test]
+fn screen_diff() {
+    let mut prev = Screen::new(2, 4);
+    prev.write("\x1b[32mab\x1b[0m\ncd");
+    let mut next = Screen::new(2, 4);
+    next.write("\x1b[32mab\x1b[0m\ncd");
+    assert_eq!(next.diff(Some(&prev)), "");
+    next.write("\x1b[2;2Hx");
+    assert_eq!(next.diff(Some(&prev)), "\x1b[2;2H\x1b[0mx\x1b[0m");
+}
+
+#[test]
+fn tonal_scale() {
+    let blue = [59.0, 130.0, 246.0];
+    let steps = scale(blue, 11, &ScaleKind::Tonal);
+    //tailwind's blue-500 lands on 500
+    assert_eq!(steps[5], blue);
+    let lightness: Vec<f64> = steps.iter().map(|c| rgb2oklch(c[0], c[1], c[2]).0).collect();
+    assert!(lightness.windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn cvd_simulation() {
+    //pure primaries pick out the columns of the Machado et al. matrices
+    assert_eq!(simulate_cvd(0xff0000, &Deficiency::Protan, 1.0), 0x6d5f00);
+    assert_eq!(simulate_cvd(0x00ff00, &Deficiency::Deutan, 1.0), 0xefd63a);
+    assert_eq!(simulate_cvd(0x0000ff, &Deficiency::Tritan, 1.0), 0x006b96);
+    assert_eq!(simulate_cvd(0x336699, &Deficiency::Protan, 0.0), 0x336699);
+    //severity can't be extrapolated past a full deficiency
+    assert_eq!(
+        simulate_cvd(0x336699, &Deficiency::Deutan, 2.0),
+        simulate_cvd(0x336699, &Deficiency::Deutan, 1.0)
+    );
+}
+
+#[test]
+fn hue_interpolation() {
+    //the examples from css color 4
+    assert_eq!(HueInterpolation::Shorter.fixup(30.0, 300.0), (390.0, 300.0));
+    assert_eq!(HueInterpolation::Longer.fixup(30.0, 60.0), (390.0, 60.0));
+    assert_eq!(HueInterpolation::Increasing.fixup(300.0, 30.0), (300.0, 390.0));
+    assert_eq!(HueInterpolation::Decreasing.fixup(30.0, 300.0), (390.0, 300.0));
+}
+
+#[test]
+fn mix_hue_and_alpha() {
+    //hue 350 and 10 meet at red instead of going through cyan
+    let (r, g, b) = hsl2rgb(350.0, 100.0, 50.0);
+    let (r2, g2, b2) = hsl2rgb(10.0, 100.0, 50.0);
+    let mixed = color_mix([r, g, b, 1.0], [r2, g2, b2, 1.0], 0.5, &MixSpace::HSL);
+    assert_eq!(rgb2hsl(mixed[0], mixed[1], mixed[2]).0.round() % 360.0, 0.0);
+    //a transparent color only adds transparency
+    let mixed = color_mix(
+        [255.0, 0.0, 0.0, 1.0],
+        [0.0, 0.0, 255.0, 0.0],
+        0.5,
+        &MixSpace::RGB,
+    );
+    assert_eq!(mixed, [255.0, 0.0, 0.0, 0.5]);
+}
+
+#[test]
+fn blend_modes() {
+    let top = [51.0, 102.0, 204.0, 1.0];
+    let bottom = [204.0, 153.0, 51.0, 1.0];
+    assert_eq!(
+        blend(top, bottom, &BlendMode::Multiply),
+        [41.0, 61.0, 41.0, 1.0]
+    );
+    assert_eq!(
+        blend(top, bottom, &BlendMode::Difference),
+        [153.0, 51.0, 153.0, 1.0]
+    );
+    //half transparent red over white
+    let flat = blend(
+        [255.0, 0.0, 0.0, 0.5],
+        [255.0, 255.0, 255.0, 1.0],
+        &BlendMode::Normal,
+    );
+    assert_eq!(flat, [255.0, 128.0, 128.0, 1.0]);
+}
+
+#[test]
+fn sass_adjustments() {
+    let adjust = |steps: &str| {
+        let steps: Vec<String> = steps.split(' ').map(|step| step.to_owned()).collect();
+        let clr = ColorRepresentation::from_rgb(0x33 as f64, 0x66 as f64, 0x99 as f64);
+        crate::adjust::parse(&steps, &ColorNameStandard::W3C)
+            .unwrap()
+            .iter()
+            .fold(clr, |clr, step| step.apply(&clr))
+    };
+    //what sass gives for #336699
+    assert_eq!(adjust("lighten 10 saturate -5 rotate 30 alpha 0.8").tohex(true), "4646b9cc");
+    assert_eq!(adjust("darken 10").tohex(false), "264d73");
+    assert_eq!(adjust("complement").tohex(false), "996633");
+    assert_eq!(adjust("grayscale").tohex(false), "666666");
+    assert_eq!(adjust("mix red").tohex(false), "99334d");
+    assert_eq!(adjust("mix red:25").tohex(false), "664d73");
+    assert!(crate::adjust::parse(&["mix".to_owned(), "blue:abc".to_owned()], &ColorNameStandard::W3C).is_err());
+}
+
+#[test]
+fn seeded_random() {
+    let constraints = Constraints::default();
+    let first = constraints.generate(&mut Rng::new(42), 5, true).unwrap();
+    let second = constraints.generate(&mut Rng::new(42), 5, true).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first.len(), 5);
+
+    let bg = [0x1a as f64, 0x1b as f64, 0x26 as f64];
+    let constraints = Constraints {
+        hue: parse_hue_range("330-30").unwrap(),
+        lightness: (0.6, 0.8),
+        chroma: (0.1, 0.2),
+        contrast: Some((bg, 4.5, ContrastAlgorithm::WCAG)),
+    };
+    for golden in [true, false] {
+        for [r, g, b] in constraints.generate(&mut Rng::new(7), 20, golden).unwrap() {
+            let (l, c, h) = rgb2oklch(r, g, b);
+            //rounding to whole rgb values moves the color a little
+            assert!((0.595..=0.805).contains(&l), "lightness {}", l);
+            assert!((0.095..=0.205).contains(&c), "chroma {}", c);
+            assert!(!(32.0..=328.0).contains(&h), "hue {}", h);
+            assert!(contrast([r, g, b], bg) >= 4.5);
+        }
+    }
+
+    let impossible = Constraints {
+        lightness: (0.0, 0.1),
+        contrast: Some((bg, 7.0, ContrastAlgorithm::WCAG)),
+        ..Constraints::default()
+    };
+    assert!(impossible.generate(&mut Rng::new(42), 3, false).is_err());
+}
+
+#[test]
+fn extract_palette() {
+    let path = std::env::temp_dir().join("tpick-extract-test.ppm");
+    std::fs::write(&path, "P3 2 1 255\n255 0 0 0 0 255\n").unwrap();
+    let image = crate::image::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    for method in [ExtractMethod::KMeans, ExtractMethod::MeanCut] {
+        let mut colors: Vec<_> = extract(&image, 2, &method)
+            .unwrap()
+            .iter()
+            .map(|swatch| (swatch.rgb.map(f64::round), swatch.proportion))
+            .collect();
+        colors.sort_by(|a, b| a.0[0].total_cmp(&b.0[0]));
+        assert_eq!(colors, [([0.0, 0.0, 255.0], 0.5), ([255.0, 0.0, 0.0], 0.5)]);
+    }
+}
+
+#[test]
+fn lopsided_mean_cut() {
+    //three quarters red and a quarter blue, a cut at the median would land inside the red
+    let mut pixels = vec![[255, 0, 0, 255]; 48];
+    pixels.extend([[0, 0, 255, 255]; 16]);
+    let image = Image {
+        width: 8,
+        height: 8,
+        pixels,
+    };
+    let colors: Vec<_> = extract(&image, 2, &ExtractMethod::MeanCut)
+        .unwrap()
+        .iter()
+        .map(|swatch| (swatch.rgb.map(f64::round), swatch.proportion))
+        .collect();
+    assert_eq!(colors, [([255.0, 0.0, 0.0], 0.75), ([0.0, 0.0, 255.0], 0.25)]);
+}
+
+#[test]
+fn decode_png() {
+    let rgb = |x: u8, y: u8| [x * 30 + 10, y * 25 + 5, (x as u16 * y as u16 * 7 % 256) as u8];
+    let expected = |width: u8, height: u8, px: &dyn Fn(u8, u8) -> [u8; 4]| -> Vec<[u8; 4]> {
+        (0..height).flat_map(|y| (0..width).map(move |x| px(x, y))).collect()
+    };
+    //a row of each filter type
+    let image = png::decode(include_bytes!("../tests/fixtures/filters.png")).unwrap();
+    assert_eq!((image.width, image.height), (7, 5));
+    assert_eq!(image.pixels, expected(7, 5, &|x, y| {
+        let [r, g, b] = rgb(x, y);
+        [r, g, b, 255]
+    }));
+    let image = png::decode(include_bytes!("../tests/fixtures/interlaced.png")).unwrap();
+    assert_eq!(image.pixels, expected(9, 9, &|x, y| {
+        let [r, g, b] = rgb(x, y);
+        [r, g, b, (x + y) * 14]
+    }));
+    //16 bit samples keep their high byte
+    let image = png::decode(include_bytes!("../tests/fixtures/rgb16.png")).unwrap();
+    assert_eq!(image.pixels, expected(3, 2, &|x, y| {
+        let [r, g, b] = rgb(x, y);
+        [r, g, b, 255]
+    }));
+    //2 bit indices, only the first two palette entries have a tRNS alpha
+    let palette = [[255, 0, 0, 0], [0, 255, 0, 128], [0, 0, 255, 255], [255, 255, 255, 255]];
+    let image = png::decode(include_bytes!("../tests/fixtures/palette.png")).unwrap();
+    assert_eq!(image.pixels, expected(5, 3, &|x, y| palette[(x as usize + y as usize) % 4]));
+}
+
+#[test]
+fn decode_jpeg() {
+    //4:2:0 with a restart marker after every MCU, the width and height are not whole MCUs
+    let data = include_bytes!("../tests/fixtures/subsampled.jpg");
+    let image = jpeg::decode(data).unwrap();
+    assert_eq!((image.width, image.height), (40, 24));
+    for (i, px) in image.pixels.iter().enumerate() {
+        let (x, y) = (i % 40, i / 40);
+        let base = [[150, 40, 40], [40, 60, 150], [60, 140, 60]][x / 16];
+        for c in 0..3 {
+            let expected = base[c] + (x % 16 + y % 16) as i32 * 3;
+            assert!((px[c] as i32 - expected).abs() <= 3, "{:?} at {}, {}", px, x, y);
+        }
+    }
+}
+
+#[test]
+fn corrupt_images() {
+    let png_data = include_bytes!("../tests/fixtures/filters.png");
+    let jpeg_data = include_bytes!("../tests/fixtures/subsampled.jpg");
+    for len in [20, 33, png_data.len() - 30] {
+        assert!(png::decode(&png_data[..len]).is_err());
+    }
+    for len in [10, 200, jpeg_data.len() - 100] {
+        assert!(jpeg::decode(&jpeg_data[..len]).is_err());
+    }
+    //a 1x1 image whose data inflates to a megabyte
+    assert!(png::decode(include_bytes!("../tests/fixtures/bomb.png")).is_err());
+    //an IHDR of 0 by 1 pixels
+    let mut zero_width = png_data.to_vec();
+    zero_width[16..24].copy_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+    assert!(png::decode(&zero_width).is_err());
+    //a PLTE chunk whose length isn't a multiple of 3
+    let mut plte = png_data[..33].to_vec();
+    plte.extend([0, 0, 0, 4, b'P', b'L', b'T', b'E', 255, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(png::decode(&plte).is_err());
+    //a 65535 by 65535 frame, a scan header claiming more components than it has and a DC
+    //difference that is too big
+    let frame = [0xff, 0xd8, 0xff, 0xc0, 0, 11, 8, 0xff, 0xff, 0xff, 0xff, 1, 1, 0x11, 0];
+    assert!(jpeg::decode(&frame).is_err());
+    let frame = [0xff, 0xd8, 0xff, 0xc0, 0, 11, 8, 0, 1, 0, 1, 1, 1, 0x11, 0];
+    let mut scan = frame.to_vec();
+    scan.extend([0xff, 0xda, 0, 4, 1, 1, 0xff, 0xd9]);
+    assert!(jpeg::decode(&scan).is_err());
+    let mut dc = frame.to_vec();
+    //a DC table with a single 1 bit code for a size of 40
+    dc.extend([0xff, 0xc4, 0, 20, 0, 1]);
+    dc.extend([0; 15]);
+    dc.extend([40, 0xff, 0xda, 0, 8, 1, 1, 0, 0, 0x3f, 0, 0, 0, 0, 0, 0, 0xff, 0xd9]);
+    assert!(jpeg::decode(&dc).is_err());
+}
+
+#[test]
+fn decode_pnm() {
+    let path = std::env::temp_dir().join("tpick-pnm-test.ppm");
+    for data in ["P6 1 1 255", "P6 0 1 255\n", "P4 1 1\n\0", "P5 70000 70000 255\n\0"] {
+        std::fs::write(&path, data).unwrap();
+        assert!(crate::image::load(&path).is_err(), "{:?}", data);
+    }
+    std::fs::write(&path, "P2 2 1 # a comment\n15 0 15\n").unwrap();
+    let image = crate::image::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(image.pixels, [[0, 0, 0, 255], [255, 255, 255, 255]]);
+}
+
+#[test]
+fn color_name_file() {
+    let path = std::env::temp_dir().join("tpick-names-test.txt");
+    std::fs::write(&path, "! a comment\nbrand = rebecca purple\n0 0 255 deep sea\n").unwrap();
+    let std = ColorNameStandard::Custom(path.clone());
+    //names in the file can refer to w3c names
+    assert_eq!(name_to_hex("brand", &std).as_deref(), Some("#663399"));
+    assert_eq!(name_to_hex("deep sea", &std).as_deref(), Some("#0000ff"));
+    assert_eq!(name_to_hex("red", &std).as_deref(), Some("#ff0000"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn bright_black() {
+    //outside of xterm and the user's terminal bright black is gray, not red
+    for std in [ColorNameStandard::W3C, ColorNameStandard::X11] {
+        assert_eq!(name_to_hex("bright black", &std).as_deref(), Some("#808080"));
+        assert_eq!(name_to_hex("90", &std).as_deref(), Some("#808080"));
+    }
+    let xterm = name_to_hex("bright black", &ColorNameStandard::XTerm);
+    assert_eq!(xterm.as_deref(), Some("#4d4d4d"));
+}
+
+#[test]
+fn invalid_colors() {
+    let std = ColorNameStandard::W3C;
+    for clr in [
+        "#12345z",
+        "#ab",
+        "rgb(1,2)",
+        "rgb",
+        "rgba(1,2,3)",
+        "hsl(",
+        "hsla(0,0,0,x)",
+        "1;2",
+        "\\x1b[38",
+    ] {
+        assert!(
+            ColorRepresentation::try_from_color(clr, &std).is_err(),
+            "{}",
+            clr
+        );
+    }
+    let clr = ColorRepresentation::try_from_color("rgba(10, 20, 30, 64)", &std).unwrap();
+    assert_eq!((clr.rgb(), clr.a), ((10.0, 20.0, 30.0), 64));
+}
+
+#[test]
+fn css_alpha() {
+    let alpha = |clr: &str| {
+        ColorRepresentation::try_from_color(clr, &ColorNameStandard::W3C).map(|clr| clr.a)
+    };
+    assert_eq!(alpha("rgba(255, 0, 0, 0.5)"), Ok(128));
+    assert_eq!(alpha("rgba(255, 0, 0, 50%)"), Ok(128));
+    assert_eq!(alpha("rgba(255, 0, 0, 1.0)"), Ok(255));
+    assert_eq!(alpha("rgba(255, 0, 0, 128)"), Ok(128));
+    assert_eq!(alpha("hsla(0, 100, 50, 25%)"), Ok(64));
+    assert!(alpha("rgba(255, 0, 0, 300)").is_err());
+    assert!(alpha("rgba(255, 0, 0, 150%)").is_err());
+}
+
+#[test]
+fn baseline_color_names() {
+    //every name tpick knew before the full css and x11 tables were added
+    let names = [
+        "alice blue", "antique white", "aqua", "aquamarine", "azure", "beige", "bisque",
+        "blanched almond", "blue violet", "brown", "burlywood", "cadet blue", "chartreuse",
+        "chocolate", "coral", "cornflower blue", "cornsilk", "crimson", "dark blue", "dark cyan",
+        "dark goldenrod", "dark gray", "dark green", "dark khaki", "dark magenta",
+        "dark olive green", "dark orange", "dark orchid", "dark red", "dark salmon",
+        "dark sea green", "dark slate blue", "dark slate gray", "dark turquoise", "dark violet",
+        "deep pink", "deep sky blue", "dim gray", "dodger blue", "firebrick", "floral white",
+        "forest green", "fuchsia", "gainsboro", "ghost white", "gold", "goldenrod", "gray",
+        "web gray", "web green", "green yellow", "honeydew", "hot pink", "indian red", "indigo",
+        "ivory", "khaki", "lavender", "lavender blush", "lawn green", "lemon chiffon",
+        "light blue", "light coral", "light cyan", "light goldenrod", "light gray", "light green",
+        "light pink", "light salmon", "light sea green", "light sky blue", "light slate gray",
+        "light steel blue", "light yellow", "lime", "lime green", "linen", "maroon", "web maroon",
+        "medium aquamarine", "medium blue", "medium orchid", "medium purple", "medium sea green",
+        "medium slate blue", "medium spring green", "medium turquoise", "medium violet red",
+        "midnight blue", "mint cream", "misty rose", "moccasin", "navajo white", "navy blue",
+        "old lace", "olive", "olive drab", "orange", "orange red", "orchid", "pale goldenrod",
+        "pale green", "pale turquoise", "pale violet red", "papaya whip", "peach puff", "peru",
+        "pink", "plum", "powder blue", "purple", "web purple", "rebecca purple", "rosy brown",
+        "royal blue", "saddle brown", "salmon", "sandy brown", "sea green", "seashell", "sienna",
+        "silver", "sky blue", "slate blue", "slate gray", "snow", "spring green", "steel blue",
+        "tan", "teal", "thistle", "tomato", "turquoise", "violet", "wheat", "white smoke",
+        "yellow green",
+        "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white", "bright black",
+        "bright red", "bright green", "bright yellow", "bright blue", "bright magenta",
+        "bright cyan", "bright white", "0", "31", "97",
+    ];
+    for standard in [ColorNameStandard::W3C, ColorNameStandard::X11, ColorNameStandard::XTerm] {
+        for name in names {
+            assert!(name_to_hex(name, &standard).is_some(), "{} in {:?}", name, standard);
+        }
+    }
+    assert_eq!(
+        name_to_hex("rebecca purple", &ColorNameStandard::X11).as_deref(),
+        Some("#663399")
+    );
+}
@@ -1,17 +1,31 @@
-use std::{
-    fmt::Display,
-    io::{Read, Write},
-};
+use std::{fmt::Display, io::Write};
 
-pub fn input(prompt: &str, reader: &mut std::io::Stdin, row: u32, col: u32) -> String {
+use crate::read_terminal;
+
+///Reads the bytes of a key press, retrying when a resize interrupts the wait
+///returns None once the terminal is closed
+fn read_key(b: &mut [u8]) -> Option<usize> {
+    loop {
+        let bytes_read = read_terminal(b);
+        if bytes_read > 0 {
+            return Some(bytes_read as usize);
+        }
+        if bytes_read == 0
+            || std::io::Error::last_os_error().kind() != std::io::ErrorKind::Interrupted
+        {
+            return None;
+        }
+    }
+}
+
+pub fn input(prompt: &str, row: u32, col: u32) -> String {
     eprint!("\x1b[s");
     eprint!("\x1b[?25h");
     eprint!("\x1b[{};{}H\x1b[2K{}", row, col, prompt);
     let _ = std::io::stdout().flush();
     let mut data = String::new();
     let mut b = [0; 32];
-    loop {
-        let bytes_read = reader.read(&mut b).unwrap();
+    while let Some(bytes_read) = read_key(&mut b) {
         if b[0] == 10 {
             break;
         }
@@ -27,12 +41,13 @@ pub fn input(prompt: &str, reader: &mut std::io::Stdin, row: u32, col: u32) -> S
     eprint!("\x1b[?25l");
     eprint!("\x1b[2K");
     eprint!("\x1b[u");
+    //the prompt was drawn over the last frame
+    crate::screen::invalidate();
     return data;
 }
 
 pub fn selection_menu<T: Display + Clone>(
     items: Vec<T>,
-    reader: &mut std::io::Stdin,
     row: u32,
     col: u32,
 ) -> T {
@@ -48,8 +63,7 @@ pub fn selection_menu<T: Display + Clone>(
             }
         }
         let mut b = [0 as u8; 1];
-        reader.read(&mut b).unwrap();
-        if b[0] == 10 {
+        if read_key(&mut b).is_none() || b[0] == 10 {
             break;
         }
         if b[0] >= 48 && b[0] - 48 < items.len() as u8 {
@@ -74,5 +88,6 @@ pub fn selection_menu<T: Display + Clone>(
         eprintln!("\x1b[2K");
     }
     eprint!("\x1b[u");
+    crate::screen::invalidate();
     return items[curr_selection].clone();
 }